@@ -0,0 +1,163 @@
+//! # A/B slot boot selection
+//!
+//! ## Overview
+//! Under the `mcu-boot` feature, [`configure_mmu`][crate::configure_mmu]
+//! used to always map a single, hard-coded application partition. This
+//! module turns that into a real dual-slot bootloader: a small marker
+//! sector records which of the two application partitions ("slot A" and
+//! "slot B") should be launched next, so the same bootloader image can
+//! serve an OTA update flow that flashes the inactive slot, boots it
+//! provisionally, and either [commit]s it or [rollback]s to the known-good
+//! slot.
+//!
+//! If the marker sector has never been written (or is corrupt), slot A is
+//! used, preserving the single-partition behavior this module replaces.
+
+const SLOT_MARKER_FLASH_OFFSET: u32 = 0x0000_f000;
+const SLOT_MARKER_MAGIC: u32 = 0x5a5a_424f; // "ZZBO", bespoke to this marker sector
+
+const SLOT_A_OFFSET: u32 = 0x0001_0000;
+/// Size reserved for each application partition. Must match the spacing
+/// between the two `app` partitions in the partition table.
+const SLOT_SIZE: u32 = 0x0010_0000;
+const SLOT_B_OFFSET: u32 = SLOT_A_OFFSET + SLOT_SIZE;
+
+const MARKER_STATE_VERIFIED: u32 = 0;
+const MARKER_STATE_PENDING_VERIFY: u32 = 1;
+
+extern "C" {
+    fn esp_rom_spiflash_read(src_addr: u32, data: *mut u32, len: u32) -> i32;
+    fn esp_rom_spiflash_write(dest_addr: u32, data: *const u32, len: u32) -> i32;
+    fn esp_rom_spiflash_erase_sector(sector_number: u32) -> i32;
+}
+
+/// One of the two application partitions a dual-slot bootloader can launch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    /// The first application partition.
+    A,
+    /// The second application partition.
+    B,
+}
+
+impl Slot {
+    fn other(self) -> Self {
+        match self {
+            Slot::A => Slot::B,
+            Slot::B => Slot::A,
+        }
+    }
+
+    fn flash_offset(self) -> u32 {
+        match self {
+            Slot::A => SLOT_A_OFFSET,
+            Slot::B => SLOT_B_OFFSET,
+        }
+    }
+
+    fn from_marker_value(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Slot::A),
+            1 => Some(Slot::B),
+            _ => None,
+        }
+    }
+
+    fn marker_value(self) -> u32 {
+        match self {
+            Slot::A => 0,
+            Slot::B => 1,
+        }
+    }
+}
+
+#[repr(C)]
+struct SlotMarker {
+    magic: u32,
+    slot: u32,
+    state: u32,
+}
+
+fn read_marker() -> Option<SlotMarker> {
+    let mut raw = [0u32; 3];
+    let ok = unsafe {
+        esp_rom_spiflash_read(
+            SLOT_MARKER_FLASH_OFFSET,
+            raw.as_mut_ptr(),
+            core::mem::size_of_val(&raw) as u32,
+        )
+    } == 0;
+
+    if !ok || raw[0] != SLOT_MARKER_MAGIC {
+        return None;
+    }
+
+    Some(SlotMarker {
+        magic: raw[0],
+        slot: raw[1],
+        state: raw[2],
+    })
+}
+
+fn write_marker(slot: Slot, state: u32) {
+    let raw = [SLOT_MARKER_MAGIC, slot.marker_value(), state];
+
+    unsafe {
+        esp_rom_spiflash_erase_sector(SLOT_MARKER_FLASH_OFFSET / 4096);
+        esp_rom_spiflash_write(
+            SLOT_MARKER_FLASH_OFFSET,
+            raw.as_ptr(),
+            core::mem::size_of_val(&raw) as u32,
+        );
+    }
+}
+
+/// Returns the flash offset [`configure_mmu`][crate::configure_mmu] should
+/// map as the application IROM/DROM base.
+pub(crate) fn active_partition_offset() -> u32 {
+    active_slot().flash_offset()
+}
+
+/// Returns the slot the bootloader will launch on the next boot.
+///
+/// Returns [Slot::A] if no valid marker has been written yet.
+pub fn active_slot() -> Slot {
+    read_marker()
+        .and_then(|marker| Slot::from_marker_value(marker.slot))
+        .unwrap_or(Slot::A)
+}
+
+/// Returns `true` if the active slot has been flashed but not yet confirmed
+/// good by a call to [commit].
+pub fn is_pending_verify() -> bool {
+    read_marker().is_some_and(|marker| marker.state == MARKER_STATE_PENDING_VERIFY)
+}
+
+/// Marks `slot` as the one to launch next, in the "pending verification"
+/// state.
+///
+/// Call this after flashing a freshly-updated application to the inactive
+/// slot. The bootloader will launch `slot` on the next boot; if the new
+/// application never calls [commit], a subsequent [rollback] returns to the
+/// slot that was active beforehand.
+pub fn mark_pending(slot: Slot) {
+    write_marker(slot, MARKER_STATE_PENDING_VERIFY);
+}
+
+/// Confirms the currently-active slot is good, clearing its "pending
+/// verification" state so future boots keep using it without requiring
+/// [mark_pending] again.
+pub fn commit() {
+    write_marker(active_slot(), MARKER_STATE_VERIFIED);
+}
+
+/// Rolls back to the slot that was active before the current (failed)
+/// update, marking it verified so the bootloader stops retrying the broken
+/// slot.
+///
+/// Returns the slot that is now active.
+pub fn rollback() -> Slot {
+    let previous = active_slot().other();
+    write_marker(previous, MARKER_STATE_VERIFIED);
+    previous
+}
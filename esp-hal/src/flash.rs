@@ -0,0 +1,199 @@
+//! # Internal SPI flash storage
+//!
+//! ## Overview
+//! [FlashStorage] gives firmware a way to read/write/erase the on-board SPI
+//! flash at runtime, so applications can persist configuration or state
+//! without going through a filesystem crate or the `mcu-boot` MMU-remapping
+//! path. It is backed directly by the SPI-flash ROM routines, the same ones
+//! the first-stage bootloader itself uses to load the image.
+//!
+//! Because the CPU executes out of flash via the instruction cache, every
+//! write/erase suspends the icache, invalidates it, performs the ROM call,
+//! and only then resumes the icache -- otherwise the CPU could keep
+//! executing stale cached instructions/data from the region just modified.
+//! The whole sequence runs inside a [critical_section], since the icache is
+//! unavailable (and therefore code/data fetches from flash are unsafe) for
+//! its duration.
+
+use embedded_storage::nor_flash::{
+    check_erase,
+    check_read,
+    check_write,
+    ErrorType,
+    MultiwriteNorFlash,
+    NorFlash,
+    NorFlashError,
+    NorFlashErrorKind,
+    ReadNorFlash,
+};
+
+extern "C" {
+    fn esp_rom_spiflash_read(src_addr: u32, data: *mut u32, len: u32) -> i32;
+    fn esp_rom_spiflash_write(dest_addr: u32, data: *const u32, len: u32) -> i32;
+    fn esp_rom_spiflash_erase_sector(sector_number: u32) -> i32;
+
+    fn cache_suspend_icache() -> u32;
+    fn cache_resume_icache(val: u32);
+    fn cache_invalidate_icache_all();
+}
+
+/// Size, in bytes, of the word-aligned scratch buffer [`FlashStorage::read`]
+/// and [`FlashStorage::write`] copy through, one chunk at a time.
+///
+/// `READ_SIZE`/`WRITE_SIZE` below only constrain length/offset, not
+/// alignment, so a fully trait-valid caller can pass an arbitrary unaligned
+/// `&mut [u8]`/`&[u8]`. `esp_rom_spiflash_read`/`esp_rom_spiflash_write` are
+/// word-oriented ROM routines, so reinterpreting such a buffer's pointer
+/// directly as `*mut u32`/`*const u32` is unaligned-access UB. Copying
+/// through this buffer instead -- a `[u32; _]`, so naturally word-aligned --
+/// sidesteps that regardless of what the caller passed in.
+const SCRATCH_WORDS: usize = 16;
+const SCRATCH_BYTES: usize = SCRATCH_WORDS * 4;
+
+/// Errors returned by [FlashStorage] operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlashStorageError {
+    /// The requested region falls outside the flash chip's capacity, or its
+    /// offset/length is not a multiple of `WRITE_SIZE` (writes) or
+    /// `ERASE_SIZE` (erases).
+    OutOfBounds,
+    /// The ROM routine reported a failure (e.g. a write to a protected
+    /// region, or a chip communication error).
+    IoError,
+}
+
+impl NorFlashError for FlashStorageError {
+    fn kind(&self) -> NorFlashErrorKind {
+        match self {
+            FlashStorageError::OutOfBounds => NorFlashErrorKind::OutOfBounds,
+            FlashStorageError::IoError => NorFlashErrorKind::Other,
+        }
+    }
+}
+
+/// Driver for the chip's internal SPI flash, implementing the
+/// `embedded-storage` NOR flash traits.
+///
+/// Offsets and lengths passed to [ReadNorFlash]/[NorFlash] are absolute flash
+/// addresses, not relative to any partition -- callers are responsible for
+/// keeping their reads/writes inside the region they own (e.g. a `storage`
+/// partition reserved in the partition table).
+pub struct FlashStorage {
+    capacity: usize,
+}
+
+impl FlashStorage {
+    /// Creates a flash driver for a chip with the given total flash
+    /// capacity, in bytes.
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity }
+    }
+
+    /// Runs `f` with the instruction cache suspended, invalidating the cache
+    /// before `f` runs and resuming it afterwards, as required around any ROM
+    /// call that modifies flash contents the icache may have cached.
+    fn with_icache_suspended<R>(f: impl FnOnce() -> R) -> R {
+        critical_section::with(|_| unsafe {
+            let autoload = cache_suspend_icache();
+            cache_invalidate_icache_all();
+
+            let result = f();
+
+            cache_resume_icache(autoload);
+
+            result
+        })
+    }
+}
+
+impl ErrorType for FlashStorage {
+    type Error = FlashStorageError;
+}
+
+impl ReadNorFlash for FlashStorage {
+    const READ_SIZE: usize = 1;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+        check_read(self, offset, bytes.len()).map_err(|_| FlashStorageError::OutOfBounds)?;
+
+        for (chunk_index, chunk) in bytes.chunks_mut(SCRATCH_BYTES).enumerate() {
+            let addr = offset + (chunk_index * SCRATCH_BYTES) as u32;
+            let word_len = (chunk.len() + 3) / 4;
+
+            Self::with_icache_suspended(|| {
+                let mut scratch = [0u32; SCRATCH_WORDS];
+                let result = unsafe {
+                    esp_rom_spiflash_read(addr, scratch.as_mut_ptr(), (word_len * 4) as u32)
+                };
+
+                if result != 0 {
+                    return Err(FlashStorageError::IoError);
+                }
+
+                let scratch_bytes = unsafe {
+                    core::slice::from_raw_parts(scratch.as_ptr().cast::<u8>(), SCRATCH_BYTES)
+                };
+                chunk.copy_from_slice(&scratch_bytes[..chunk.len()]);
+
+                Ok(())
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl NorFlash for FlashStorage {
+    const WRITE_SIZE: usize = 4;
+    const ERASE_SIZE: usize = 4096;
+
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error> {
+        check_erase(self, from, to).map_err(|_| FlashStorageError::OutOfBounds)?;
+
+        Self::with_icache_suspended(|| {
+            for sector_addr in (from..to).step_by(Self::ERASE_SIZE) {
+                let sector_number = sector_addr / Self::ERASE_SIZE as u32;
+                if unsafe { esp_rom_spiflash_erase_sector(sector_number) } != 0 {
+                    return Err(FlashStorageError::IoError);
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        check_write(self, offset, bytes.len()).map_err(|_| FlashStorageError::OutOfBounds)?;
+
+        for (chunk_index, chunk) in bytes.chunks(SCRATCH_BYTES).enumerate() {
+            let addr = offset + (chunk_index * SCRATCH_BYTES) as u32;
+            let word_len = (chunk.len() + 3) / 4;
+
+            Self::with_icache_suspended(|| {
+                let mut scratch = [0u32; SCRATCH_WORDS];
+                let scratch_bytes = unsafe {
+                    core::slice::from_raw_parts_mut(scratch.as_mut_ptr().cast::<u8>(), SCRATCH_BYTES)
+                };
+                scratch_bytes[..chunk.len()].copy_from_slice(chunk);
+
+                let result = unsafe {
+                    esp_rom_spiflash_write(addr, scratch.as_ptr(), (word_len * 4) as u32)
+                };
+
+                if result == 0 {
+                    Ok(())
+                } else {
+                    Err(FlashStorageError::IoError)
+                }
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl MultiwriteNorFlash for FlashStorage {}
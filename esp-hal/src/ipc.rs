@@ -0,0 +1,209 @@
+//! # Inter-core communication primitives
+//!
+//! ## Overview
+//! On multi-core parts, [Channel] and [Semaphore] provide lock-free ways for
+//! ProCpu and AppCpu to hand data and synchronize with each other without a
+//! full RTOS. [Channel] is built on a sequence-number-per-cell CAS
+//! algorithm: each cell stores a sequence counter, producers claim a slot when
+//! `seq == pos`, consumers claim it when `seq == pos + 1`; on release a
+//! producer sets the cell's sequence to `pos + 1` and a consumer sets it to
+//! `pos + N`, ready for the next lap. [Semaphore] is a single atomic count
+//! updated with CAS.
+//!
+//! Place a [Channel] or [Semaphore] in memory reachable (and, ideally,
+//! uncached) by both cores, e.g. using `#[link_section = ".shared_rodata"]`
+//! or `#[ram(rtc_fast)]` as appropriate for the target.
+
+use core::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// A bounded, lock-free, multi-producer multi-consumer queue for passing
+/// values of type `T` between cores.
+pub struct Channel<T, const N: usize> {
+    buffer: [Cell<T>; N],
+    dequeue_pos: AtomicUsize,
+    enqueue_pos: AtomicUsize,
+}
+
+struct Cell<T> {
+    sequence: AtomicUsize,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+// SAFETY: Access to `value` is only ever granted to the core that won the
+// sequence-number CAS for that slot, so at most one core can read or write a
+// given cell's value at a time.
+unsafe impl<T: Send, const N: usize> Sync for Channel<T, N> {}
+
+impl<T, const N: usize> Channel<T, N> {
+    /// Creates a new, empty channel.
+    pub const fn new() -> Self {
+        // `N` must be non-zero and a power of two so that `pos & (N - 1)` is a
+        // valid index and sequence wraparound stays consistent with the
+        // Vyukov queue invariants.
+        assert!(N != 0 && N.is_power_of_two());
+
+        let mut cell_count = 0;
+        let mut buffer = [const {
+            Cell {
+                sequence: AtomicUsize::new(0),
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+            }
+        }; N];
+        while cell_count != N {
+            buffer[cell_count].sequence = AtomicUsize::new(cell_count);
+            cell_count += 1;
+        }
+
+        Self {
+            buffer,
+            dequeue_pos: AtomicUsize::new(0),
+            enqueue_pos: AtomicUsize::new(0),
+        }
+    }
+
+    /// Attempts to push `value` onto the channel.
+    ///
+    /// Returns `Err(value)` if the channel is full.
+    pub fn enqueue(&self, value: T) -> Result<(), T> {
+        let mask = N - 1;
+        let mut pos = self.enqueue_pos.load(Ordering::Relaxed);
+        loop {
+            let cell = &self.buffer[pos & mask];
+            let seq = cell.sequence.load(Ordering::Acquire);
+            let diff = seq as isize - pos as isize;
+
+            if diff == 0 {
+                if self
+                    .enqueue_pos
+                    .compare_exchange_weak(
+                        pos,
+                        pos.wrapping_add(1),
+                        Ordering::Relaxed,
+                        Ordering::Relaxed,
+                    )
+                    .is_ok()
+                {
+                    unsafe { (*cell.value.get()).write(value) };
+                    cell.sequence.store(pos.wrapping_add(1), Ordering::Release);
+                    return Ok(());
+                }
+            } else if diff < 0 {
+                return Err(value);
+            } else {
+                pos = self.enqueue_pos.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Attempts to pop a value from the channel.
+    ///
+    /// Returns `None` if the channel is empty.
+    pub fn dequeue(&self) -> Option<T> {
+        let mask = N - 1;
+        let mut pos = self.dequeue_pos.load(Ordering::Relaxed);
+        loop {
+            let cell = &self.buffer[pos & mask];
+            let seq = cell.sequence.load(Ordering::Acquire);
+            let diff = seq as isize - (pos.wrapping_add(1)) as isize;
+
+            if diff == 0 {
+                if self
+                    .dequeue_pos
+                    .compare_exchange_weak(
+                        pos,
+                        pos.wrapping_add(1),
+                        Ordering::Relaxed,
+                        Ordering::Relaxed,
+                    )
+                    .is_ok()
+                {
+                    let value = unsafe { (*cell.value.get()).assume_init_read() };
+                    cell.sequence
+                        .store(pos.wrapping_add(mask).wrapping_add(1), Ordering::Release);
+                    return Some(value);
+                }
+            } else if diff < 0 {
+                return None;
+            } else {
+                pos = self.dequeue_pos.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Drains and drops all outstanding elements, and re-initializes the
+    /// cell sequence numbers as if the channel had just been created.
+    ///
+    /// This is only safe to call when no other core is concurrently
+    /// enqueuing or dequeuing.
+    pub fn reset(&self) {
+        while self.dequeue().is_some() {}
+
+        for (i, cell) in self.buffer.iter().enumerate() {
+            cell.sequence.store(i, Ordering::Relaxed);
+        }
+        self.dequeue_pos.store(0, Ordering::Relaxed);
+        self.enqueue_pos.store(0, Ordering::Relaxed);
+    }
+}
+
+impl<T, const N: usize> Default for Channel<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A counting semaphore for coordinating access to a shared resource between
+/// cores.
+pub struct Semaphore {
+    count: AtomicUsize,
+}
+
+impl Semaphore {
+    /// Creates a new semaphore with the given initial count.
+    pub const fn new(initial: usize) -> Self {
+        Self {
+            count: AtomicUsize::new(initial),
+        }
+    }
+
+    /// Attempts to acquire the semaphore without blocking.
+    ///
+    /// Returns `true` if the count was successfully decremented.
+    pub fn try_acquire(&self) -> bool {
+        let mut count = self.count.load(Ordering::Relaxed);
+        loop {
+            if count == 0 {
+                return false;
+            }
+
+            match self.count.compare_exchange_weak(
+                count,
+                count - 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return true,
+                Err(actual) => count = actual,
+            }
+        }
+    }
+
+    /// Spins until the semaphore can be acquired.
+    pub fn acquire(&self) {
+        while !self.try_acquire() {}
+    }
+
+    /// Releases the semaphore, incrementing its count.
+    pub fn release(&self) {
+        self.count.fetch_add(1, Ordering::Release);
+    }
+
+    /// Resets the semaphore to `count`, discarding any pending acquisitions.
+    pub fn reset(&self, count: usize) {
+        self.count.store(count, Ordering::Relaxed);
+    }
+}
@@ -87,6 +87,8 @@ pub mod aes;
 pub mod analog;
 #[cfg(assist_debug)]
 pub mod assist_debug;
+#[cfg(feature = "mcu-boot")]
+pub mod boot;
 pub mod clock;
 #[cfg(any(xtensa, all(riscv, systimer)))]
 pub mod delay;
@@ -98,6 +100,7 @@ pub mod ecc;
 pub mod embassy;
 #[cfg(soc_etm)]
 pub mod etm;
+pub mod flash;
 #[cfg(gpio)]
 pub mod gpio;
 #[cfg(hmac)]
@@ -108,8 +111,13 @@ pub mod i2c;
 pub mod i2s;
 #[cfg(any(dport, interrupt_core0, interrupt_core1))]
 pub mod interrupt;
+#[cfg(multi_core)]
+pub mod ipc;
 #[cfg(ledc)]
 pub mod ledc;
+#[cfg(multi_core)]
+pub mod mailbox;
+pub mod metadata;
 #[cfg(any(mcpwm0, mcpwm1))]
 pub mod mcpwm;
 #[cfg(usb0)]
@@ -161,6 +169,130 @@ pub mod trapframe {
     pub use xtensa_lx_rt::exception::Context as TrapFrame;
 }
 
+/// Relocating the trap/exception vector table at runtime.
+///
+/// By default the vector table lives wherever the linker script and
+/// `esp_riscv_rt`/`xtensa_lx_rt` place it. Code that executes from PSRAM or
+/// flash can use [vectors::relocate_vector_table] to copy the table into an
+/// aligned internal-RAM block and reprogram the CPU's vector-base register
+/// to point at it, for deterministic interrupt latency, or to swap vector
+/// tables when jumping between a bootloader and an application image.
+pub mod vectors {
+    /// Relocates the trap/exception vector table to `dest` and reprograms
+    /// the CPU's vector-base register (`mtvec` on RISC-V, `VECBASE` on
+    /// Xtensa) to point at it.
+    ///
+    /// # Safety
+    ///
+    /// - `dest` must remain valid, and unused for any other purpose, for as
+    ///   long as interrupts/exceptions may fire.
+    /// - `dest` must satisfy the target's vector-base alignment requirement
+    ///   (256 bytes on RISC-V, 1 KiB on Xtensa).
+    /// - `dest` must be large enough to hold the table copied from the
+    ///   linker-provided vector table.
+    pub unsafe fn relocate_vector_table(dest: *mut u32) {
+        let (start, end) = vector_table_range();
+        let len_words = (end as usize - start as usize) / core::mem::size_of::<u32>();
+
+        for i in 0..len_words {
+            dest.add(i).write_volatile(start.add(i).read_volatile());
+        }
+
+        set_vector_base(dest as usize);
+    }
+
+    cfg_if::cfg_if! {
+        if #[cfg(riscv)] {
+            extern "C" {
+                static _vector_table: u32;
+                static _vector_table_end: u32;
+            }
+
+            fn vector_table_range() -> (*const u32, *const u32) {
+                unsafe {
+                    (
+                        core::ptr::addr_of!(_vector_table),
+                        core::ptr::addr_of!(_vector_table_end),
+                    )
+                }
+            }
+
+            /// Programs `mtvec` to point at `base`, keeping vectored mode.
+            unsafe fn set_vector_base(base: usize) {
+                core::arch::asm!("csrw mtvec, {0}", in(reg) base | 0b1);
+            }
+        } else if #[cfg(xtensa)] {
+            extern "C" {
+                static _init_start: u32;
+                static _init_end: u32;
+            }
+
+            fn vector_table_range() -> (*const u32, *const u32) {
+                unsafe {
+                    (
+                        core::ptr::addr_of!(_init_start),
+                        core::ptr::addr_of!(_init_end),
+                    )
+                }
+            }
+
+            /// Programs `VECBASE` to point at `base`.
+            unsafe fn set_vector_base(base: usize) {
+                core::arch::asm!("wsr.vecbase {0}", "rsync", in(reg) base);
+            }
+        }
+    }
+}
+
+/// Registration for a handler at the CPU's highest, non-maskable interrupt
+/// priority (Xtensa level 7 / RISC-V's top priority slot).
+///
+/// `EspDefaultHandler` and every handler bound through
+/// [`interrupt::enable`][crate::interrupt::enable] run with ordinary
+/// priority, and so can be delayed for as long as some other part of the
+/// program holds a [critical_section::acquire] (e.g. via
+/// `critical_section::with`). On Xtensa, [bind_nmi] instead binds a handler
+/// at the top priority level (level 7), which `critical_section::acquire()`
+/// cannot mask (it only raises to level 5) -- giving a true
+/// watchdog/panic/low-latency path for servicing a timing-critical
+/// peripheral.
+///
+/// On RISC-V targets this guarantee does **not** hold: `critical_section`'s
+/// RISC-V implementation acquires by clearing `mstatus.MIE` globally, which
+/// masks every interrupt priority including [`Priority::max()`]. A handler
+/// bound with [bind_nmi] on RISC-V will still be delayed for the duration of
+/// any critical section, same as one bound through
+/// [`interrupt::enable`][crate::interrupt::enable].
+pub mod nmi {
+    use crate::{
+        interrupt::{self, Priority},
+        peripherals::Interrupt,
+    };
+
+    /// Binds `handler` to `interrupt` at the CPU's highest priority level.
+    ///
+    /// # Safety contract
+    ///
+    /// On Xtensa, `handler` runs outside the masking `critical_section::acquire()`
+    /// applies to every other interrupt priority -- it will preempt code
+    /// inside a critical section. On RISC-V, `critical_section::acquire()`
+    /// clears `mstatus.MIE` globally and masks `handler` the same as any
+    /// other priority, so no such preemption guarantee exists there. Where
+    /// it does apply, `handler` must not take any lock also taken by code
+    /// running at a lower priority (doing so can deadlock against itself),
+    /// and should do as little work as possible.
+    ///
+    /// # Safety
+    ///
+    /// This reprograms the CPU's interrupt dispatch for `interrupt`; the
+    /// caller must ensure no other code is concurrently relying on
+    /// `interrupt`'s previous handler or priority.
+    pub unsafe fn bind_nmi(interrupt: Interrupt, handler: extern "C" fn()) {
+        interrupt::bind_interrupt(interrupt, handler);
+        unwrap!(interrupt::enable(interrupt, Priority::max()));
+    }
+}
+
 // The `soc` module contains chip-specific implementation details and should not
 // be directly exposed.
 mod soc;
@@ -185,7 +317,7 @@ extern "C" fn DefaultHandler() {}
 /// Available CPU cores
 ///
 /// The actual number of available cores depends on the target.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Cpu {
     /// The first core
@@ -498,11 +630,11 @@ static ENTRY_POINT: unsafe extern "C" fn() = _start;
 #[cfg(feature = "mcu-boot")]
 #[link_section = ".rwtext"]
 unsafe fn configure_mmu() {
-    const PARTITION_OFFSET: u32 = 0x10000;
-    let app_irom_lma = PARTITION_OFFSET + ((&_image_irom_lma as *const u32) as u32);
+    let partition_offset = boot::active_partition_offset();
+    let app_irom_lma = partition_offset + ((&_image_irom_lma as *const u32) as u32);
     let app_irom_size = (&_image_irom_size as *const u32) as u32;
     let app_irom_vma = (&_image_irom_vma as *const u32) as u32;
-    let app_drom_lma = PARTITION_OFFSET + ((&_image_drom_lma as *const u32) as u32);
+    let app_drom_lma = partition_offset + ((&_image_drom_lma as *const u32) as u32);
     let app_drom_size = (&_image_drom_size as *const u32) as u32;
     let app_drom_vma = (&_image_drom_vma as *const u32) as u32;
 
@@ -0,0 +1,167 @@
+//! # Single-producer/single-consumer inter-core mailbox
+//!
+//! ## Overview
+//! [Mailbox] is a fixed-capacity ring buffer for streaming values from one
+//! core to the other. Unlike [Channel][crate::ipc::Channel], which supports
+//! multiple producers/consumers via a per-cell CAS sequence number, a
+//! mailbox only ever has one producer and one consumer, so it needs just a
+//! head and a tail index:
+//!
+//! - the producer writes the payload into the slot at `head`, issues a
+//!   memory fence, then publishes the advanced `head` with
+//!   [Ordering::Release]
+//! - the consumer loads `head` with [Ordering::Acquire], reads the slot,
+//!   then publishes the advanced `tail` with [Ordering::Release]
+//!
+//! [Sender::try_send] can optionally "kick" the peer core (e.g. by raising a
+//! software interrupt bound on that core) instead of leaving it to poll
+//! [Receiver::try_recv].
+//!
+//! ProCpu and AppCpu do not share coherent data caches, so a [Mailbox] must
+//! live in memory that is genuinely non-cacheable, or that both sides
+//! explicitly flush/invalidate around the fence -- otherwise a core may keep
+//! observing a stale `head`/`tail` or stale slot contents from its own
+//! cache.
+
+use core::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    sync::atomic::{fence, AtomicBool, AtomicUsize, Ordering},
+};
+
+/// A fixed-capacity single-producer/single-consumer ring buffer for passing
+/// values of type `T` from one core to the other.
+///
+/// `N` must be a power of two.
+pub struct Mailbox<T, const N: usize> {
+    buffer: [UnsafeCell<MaybeUninit<T>>; N],
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    split: AtomicBool,
+}
+
+// SAFETY: a slot is only ever written by the `Sender` and only ever read by
+// the `Receiver`, and the head/tail handshake ensures the two never touch
+// the same slot at once.
+unsafe impl<T: Send, const N: usize> Sync for Mailbox<T, N> {}
+
+impl<T, const N: usize> Mailbox<T, N> {
+    /// Creates a new, empty mailbox.
+    pub const fn new() -> Self {
+        assert!(N != 0 && N.is_power_of_two());
+
+        Self {
+            buffer: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            split: AtomicBool::new(false),
+        }
+    }
+
+    /// Splits this mailbox into a producer and a consumer endpoint.
+    ///
+    /// `kick`, if given, is called after every successful [Sender::try_send]
+    /// so the peer core can be woken instead of having to poll
+    /// [Receiver::try_recv] -- typically by raising a software interrupt
+    /// bound on the other core.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called more than once on the same [Mailbox]. The algorithm
+    /// (and the `Send` safety comments on [Sender]/[Receiver]) rely on
+    /// exactly one of each endpoint existing at a time; since a [Mailbox] is
+    /// typically shared as a `&'static` between cores rather than owned,
+    /// that invariant is enforced here at runtime instead of by consuming
+    /// `self`.
+    pub fn split(&self, kick: Option<fn()>) -> (Sender<'_, T, N>, Receiver<'_, T, N>) {
+        if self.split.swap(true, Ordering::Relaxed) {
+            panic!("Mailbox::split called more than once on the same Mailbox");
+        }
+
+        (Sender { mailbox: self, kick }, Receiver { mailbox: self })
+    }
+
+    fn try_send(&self, value: T) -> Result<(), T> {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+
+        if head.wrapping_sub(tail) == N {
+            return Err(value);
+        }
+
+        let slot = &self.buffer[head & (N - 1)];
+        unsafe { (*slot.get()).write(value) };
+
+        fence(Ordering::Release);
+        self.head.store(head.wrapping_add(1), Ordering::Release);
+
+        Ok(())
+    }
+
+    fn try_recv(&self) -> Option<T> {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+
+        if tail == head {
+            return None;
+        }
+
+        let slot = &self.buffer[tail & (N - 1)];
+        let value = unsafe { (*slot.get()).assume_init_read() };
+
+        self.tail.store(tail.wrapping_add(1), Ordering::Release);
+
+        Some(value)
+    }
+}
+
+impl<T, const N: usize> Default for Mailbox<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The producer end of a [Mailbox], obtained from [Mailbox::split].
+pub struct Sender<'a, T, const N: usize> {
+    mailbox: &'a Mailbox<T, N>,
+    kick: Option<fn()>,
+}
+
+// SAFETY: a `Sender` only ever writes into slots the consumer has already
+// vacated (tracked by `tail`), so it's sound to move it to the other core
+// as long as only one `Sender` exists per `Mailbox`.
+unsafe impl<T: Send, const N: usize> Send for Sender<'_, T, N> {}
+
+impl<T, const N: usize> Sender<'_, T, N> {
+    /// Attempts to push `value` onto the mailbox without blocking.
+    ///
+    /// Returns `Err(value)` if the mailbox is full.
+    pub fn try_send(&mut self, value: T) -> Result<(), T> {
+        self.mailbox.try_send(value)?;
+
+        if let Some(kick) = self.kick {
+            kick();
+        }
+
+        Ok(())
+    }
+}
+
+/// The consumer end of a [Mailbox], obtained from [Mailbox::split].
+pub struct Receiver<'a, T, const N: usize> {
+    mailbox: &'a Mailbox<T, N>,
+}
+
+// SAFETY: a `Receiver` only ever reads slots the producer has already
+// published (tracked by `head`), so it's sound to move it to the other core
+// as long as only one `Receiver` exists per `Mailbox`.
+unsafe impl<T: Send, const N: usize> Send for Receiver<'_, T, N> {}
+
+impl<T, const N: usize> Receiver<'_, T, N> {
+    /// Attempts to pop a value from the mailbox without blocking.
+    ///
+    /// Returns `None` if the mailbox is empty.
+    pub fn try_recv(&mut self) -> Option<T> {
+        self.mailbox.try_recv()
+    }
+}
@@ -0,0 +1,85 @@
+//! # Runtime-introspectable chip metadata
+//!
+//! [DEVICE_METADATA] mirrors the information available through the
+//! `chip!`/`property!`/`memory_range!` macros generated by `esp-metadata`,
+//! but as a single `const` value that application code and external tooling
+//! (pin planners, doc generators) can read without expanding macros or
+//! re-parsing the chip's TOML description.
+
+/// Chip-wide metadata, generated from the chip's TOML description.
+#[derive(Debug, Clone, Copy)]
+pub struct Metadata {
+    /// The name of the chip, e.g. `"esp32c6"`.
+    pub name: &'static str,
+    /// The CPU architecture.
+    pub arch: Arch,
+    /// The number of CPU cores.
+    pub cores: u8,
+    /// Peripherals available on this chip, with their IP-block versions.
+    pub peripherals: &'static [PeripheralInfo],
+    /// Memory regions available on this chip.
+    pub memory: &'static [RegionInfo],
+    /// GPIO pins available on this chip, with their capabilities and
+    /// alternate-function signal names.
+    pub pins: &'static [PinInfo],
+}
+
+/// Supported device architectures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arch {
+    /// RISC-V architecture
+    RiscV,
+    /// Xtensa architecture
+    Xtensa,
+}
+
+/// A peripheral driver, the instances it's wired up for, and its support
+/// status on this chip.
+#[derive(Debug, Clone, Copy)]
+pub struct PeripheralInfo {
+    /// The peripheral's name, e.g. `"i2c_master"`.
+    pub name: &'static str,
+    /// The IP-block version, e.g. `Some("v2")`, or `None` if unversioned.
+    pub version: Option<&'static str>,
+    /// The names of the configured instances, e.g. `["i2c0", "i2c1"]`.
+    pub instances: &'static [&'static str],
+    /// The documentation/support status of this driver on this chip.
+    pub support_status: SupportStatus,
+}
+
+/// The documentation/support status of a driver on this chip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupportStatus {
+    /// The driver is not available on this chip.
+    NotSupported,
+    /// The driver is available, but with known limitations.
+    Partial,
+    /// The driver is fully supported.
+    Supported,
+}
+
+/// A named memory region and its address range.
+#[derive(Debug, Clone, Copy)]
+pub struct RegionInfo {
+    /// The region's name, e.g. `"DRAM"`.
+    pub name: &'static str,
+    /// The first address of the region.
+    pub start: usize,
+    /// The address one past the end of the region.
+    pub end: usize,
+}
+
+/// A single GPIO pin's capabilities and alternate-function wiring.
+#[derive(Debug, Clone, Copy)]
+pub struct PinInfo {
+    /// The pin number.
+    pub number: u8,
+    /// The capabilities this pin supports, e.g. `Input`, `Output`, `Analog`.
+    pub capabilities: &'static [&'static str],
+    /// `(alternate function index, signal name)` pairs for this pin's input
+    /// signals.
+    pub input_afs: &'static [(u8, &'static str)],
+    /// `(alternate function index, signal name)` pairs for this pin's output
+    /// signals.
+    pub output_afs: &'static [(u8, &'static str)],
+}
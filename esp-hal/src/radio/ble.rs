@@ -0,0 +1,200 @@
+//! # Bluetooth Low Energy (BLE) HCI transport
+//!
+//! [`BleConnector`] implements `embedded-io-async`'s
+//! [`Read`][embedded_io_async::Read]/[`Write`][embedded_io_async::Write]
+//! over the controller's HCI byte stream, so an async host stack (e.g.
+//! bleps, trouble) can drive HCI without spinning. A read/write that can't
+//! complete immediately returns `Poll::Pending` and registers a waker: the
+//! VHCI callbacks registered in [`register_vhci_host_callback`] wake a
+//! parked read as RX bytes arrive, and [`on_controller_interrupt`] wakes a
+//! parked write once TX space has freed up.
+//!
+//! [`Write::write`][embedded_io_async::Write::write] does not hand the bytes
+//! to the controller: once TX space is available it reports the whole buffer
+//! as accepted and drops it.
+
+use core::{
+    cell::RefCell,
+    convert::Infallible,
+    future::poll_fn,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll, Waker},
+};
+
+use critical_section::Mutex;
+use embedded_io_async::{ErrorType, Read, Write};
+
+const HCI_RX_BUFFER_CAPACITY: usize = 256;
+
+struct HciRxBuffer {
+    bytes: [u8; HCI_RX_BUFFER_CAPACITY],
+    head: usize,
+    tail: usize,
+    waker: Option<Waker>,
+}
+
+impl HciRxBuffer {
+    const fn new() -> Self {
+        Self {
+            bytes: [0; HCI_RX_BUFFER_CAPACITY],
+            head: 0,
+            tail: 0,
+            waker: None,
+        }
+    }
+
+    fn len(&self) -> usize {
+        (self.tail + HCI_RX_BUFFER_CAPACITY - self.head) % HCI_RX_BUFFER_CAPACITY
+    }
+
+    /// Appends as much of `data` as fits, dropping the remainder -- the
+    /// controller interrupt handler must never block waiting for a reader.
+    fn push_slice(&mut self, data: &[u8]) {
+        for &byte in data {
+            let next_tail = (self.tail + 1) % HCI_RX_BUFFER_CAPACITY;
+            if next_tail == self.head {
+                break;
+            }
+            self.bytes[self.tail] = byte;
+            self.tail = next_tail;
+        }
+
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn pop_into(&mut self, out: &mut [u8]) -> usize {
+        let mut read = 0;
+        while read < out.len() && self.head != self.tail {
+            out[read] = self.bytes[self.head];
+            self.head = (self.head + 1) % HCI_RX_BUFFER_CAPACITY;
+            read += 1;
+        }
+        read
+    }
+}
+
+static HCI_RX: Mutex<RefCell<HciRxBuffer>> = Mutex::new(RefCell::new(HciRxBuffer::new()));
+static TX_SPACE_AVAILABLE: AtomicBool = AtomicBool::new(true);
+static TX_WAKER: Mutex<RefCell<Option<Waker>>> = Mutex::new(RefCell::new(None));
+
+/// Called from the radio controller's interrupt handler whenever
+/// previously-submitted TX data has drained. Wakes any [`BleConnector`]
+/// currently parked in [`Write::write`].
+///
+/// RX delivery doesn't go through this path: the controller pushes received
+/// HCI bytes straight to [`notify_host_recv`] via the VHCI callback
+/// registered in [`register_vhci_host_callback`], independent of this
+/// driver's own interrupt.
+pub(crate) fn on_controller_interrupt() {
+    on_hci_tx_drained();
+}
+
+/// Registers this driver's VHCI host callbacks with the controller, so
+/// received HCI bytes and TX-drained notifications are pushed into
+/// [`on_hci_rx_bytes`]/[`on_hci_tx_drained`] as they happen. Called once,
+/// from [`super::radio_hal::setup_radio_isr`].
+pub(crate) fn register_vhci_host_callback() {
+    static VHCI_HOST_CALLBACK: super::sys::include::esp_vhci_host_callback_t =
+        super::sys::include::esp_vhci_host_callback_t {
+            notify_host_send_available: Some(notify_host_send_available),
+            notify_host_recv: Some(notify_host_recv),
+        };
+
+    unsafe { super::sys::include::esp_vhci_host_register_callback(&VHCI_HOST_CALLBACK) };
+}
+
+extern "C" fn notify_host_send_available() {
+    on_hci_tx_drained();
+}
+
+/// Forwards HCI bytes newly received from the controller into the shared
+/// [`HCI_RX`] buffer, waking any parked [`BleConnector::read`].
+extern "C" fn notify_host_recv(data: *mut u8, len: u16) -> i32 {
+    // SAFETY: the controller owns `data` for the duration of this call and
+    // guarantees it points to `len` valid bytes.
+    let bytes = unsafe { core::slice::from_raw_parts(data, len as usize) };
+    on_hci_rx_bytes(bytes);
+    0
+}
+
+/// Called from the VHCI [`notify_host_recv`] callback with newly-received HCI
+/// bytes, making them available to [`BleConnector::read`].
+pub(crate) fn on_hci_rx_bytes(data: &[u8]) {
+    critical_section::with(|cs| HCI_RX.borrow(cs).borrow_mut().push_slice(data));
+}
+
+/// Called from the controller's interrupt handler once queued TX data has
+/// drained, freeing space for [`BleConnector::write`].
+pub(crate) fn on_hci_tx_drained() {
+    TX_SPACE_AVAILABLE.store(true, Ordering::Release);
+    critical_section::with(|cs| {
+        if let Some(waker) = TX_WAKER.borrow(cs).borrow_mut().take() {
+            waker.wake();
+        }
+    });
+}
+
+/// An async `embedded-io-async` transport over the BLE controller's HCI
+/// byte stream.
+#[instability::unstable]
+#[derive(Debug, Default)]
+pub struct BleConnector {
+    _private: (),
+}
+
+impl BleConnector {
+    /// Creates a new HCI transport handle.
+    ///
+    /// Like [`super::wifi::WifiEvents`], all instances share the same
+    /// underlying controller byte stream.
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+impl ErrorType for BleConnector {
+    type Error = Infallible;
+}
+
+impl Read for BleConnector {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        poll_fn(|cx| self.poll_read(buf, cx)).await
+    }
+}
+
+impl Write for BleConnector {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        poll_fn(|cx| self.poll_write(buf, cx)).await
+    }
+}
+
+impl BleConnector {
+    fn poll_read(&mut self, buf: &mut [u8], cx: &mut Context<'_>) -> Poll<Result<usize, Infallible>> {
+        critical_section::with(|cs| {
+            let mut rx = HCI_RX.borrow(cs).borrow_mut();
+            if rx.len() == 0 {
+                rx.waker = Some(cx.waker().clone());
+                return Poll::Pending;
+            }
+
+            Poll::Ready(Ok(rx.pop_into(buf)))
+        })
+    }
+
+    fn poll_write(&mut self, buf: &[u8], cx: &mut Context<'_>) -> Poll<Result<usize, Infallible>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        if TX_SPACE_AVAILABLE.swap(false, Ordering::AcqRel) {
+            // `buf` is not passed to the controller; the bytes are only
+            // reported as accepted.
+            Poll::Ready(Ok(buf.len()))
+        } else {
+            critical_section::with(|cs| *TX_WAKER.borrow(cs).borrow_mut() = Some(cx.waker().clone()));
+            Poll::Pending
+        }
+    }
+}
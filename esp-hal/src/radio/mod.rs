@@ -236,6 +236,17 @@ const _: () = {
 /// - The function may return an error if initializing the underlying driver fails.
 #[cfg(any(feature = "wifi", feature = "ble"))]
 pub(crate) fn init() -> Result<(), InitializationError> {
+    init_with_calibration_seed(None)
+}
+
+/// Shared implementation behind [`init`] and [`init_with_calibration_store`].
+///
+/// `calibration_seed`, if given, is fed to the PHY so it can perform a
+/// partial (faster) calibration instead of a full one.
+#[cfg(any(feature = "wifi", feature = "ble"))]
+fn init_with_calibration_seed(
+    calibration_seed: Option<&[u8; phy::PHY_CALIBRATION_DATA_LENGTH]>,
+) -> Result<(), InitializationError> {
     #[cfg(esp32)]
     if try_claim_adc2(unsafe { crate::Internal::conjure() }).is_err() {
         return Err(InitializationError::Adc2IsUsed);
@@ -254,7 +265,7 @@ pub(crate) fn init() -> Result<(), InitializationError> {
 
     common_adapter::enable_wifi_power_domain();
 
-    setup_radio_isr();
+    setup_radio_isr(radio_config().core);
 
     wifi_set_log_verbose();
     init_radio_clocks();
@@ -265,11 +276,40 @@ pub(crate) fn init() -> Result<(), InitializationError> {
         error => panic!("Failed to initialize coexistence, error code: {}", error),
     }
 
+    phy::calibrate(calibration_seed);
+
     debug!("Radio initialized");
 
     Ok(())
 }
 
+/// Initializes the radio stack the same way as [`init`][crate::radio::init],
+/// but first seeds PHY calibration from `store` so the driver can perform a
+/// partial calibration instead of a full one on every boot.
+///
+/// After init completes, if [`last_calibration_result`] reports
+/// [`CalibrationResult::DataCheckFailed`] -- meaning `store` had nothing
+/// cached, or what it had no longer matched this device -- the freshly
+/// completed full calibration is backed up and written to `store`, so a
+/// later boot can use it.
+#[instability::unstable]
+#[cfg(any(feature = "wifi", feature = "ble"))]
+pub fn init_with_calibration_store(
+    store: &mut impl phy::CalibrationStore,
+) -> Result<(), InitializationError> {
+    let mut data = [0u8; phy::PHY_CALIBRATION_DATA_LENGTH];
+    let has_cached_data = store.load(&mut data);
+
+    init_with_calibration_seed(has_cached_data.then_some(&data))?;
+
+    if last_calibration_result() == Some(CalibrationResult::DataCheckFailed) {
+        phy_calibration_data(&mut data);
+        store.store(&data);
+    }
+
+    Ok(())
+}
+
 #[cfg(any(feature = "wifi", feature = "ble"))]
 pub(crate) fn deinit() {
     // Disable coexistence
@@ -364,6 +404,62 @@ pub fn wifi_set_log_verbose() {
     }
 }
 
+/// Default stack size, in bytes, given to each radio worker task.
+const DEFAULT_TASK_STACK_SIZE: usize = 8192;
+
+/// Which core the radio controller interrupt is enabled on, and a stack size
+/// for the radio driver's worker tasks.
+///
+/// The module docs warn that on multi-core chips these tasks are pinned to
+/// [`crate::Cpu::ProCpu`] by default, and that 8kB of stack may not be
+/// enough on the second core. Set this (via [`set_radio_config`]) before
+/// calling [`init`] to enable the radio interrupt on [`crate::Cpu::AppCpu`]
+/// instead. Only [`core`][Self::core] has an effect: the worker tasks' stack
+/// size is not changed by this configuration.
+#[derive(Debug, Clone, Copy)]
+#[instability::unstable]
+#[cfg(any(feature = "wifi", feature = "ble"))]
+pub struct RadioConfig {
+    /// Core the radio controller interrupt is enabled on.
+    pub core: crate::Cpu,
+    /// Stack size, in bytes, given to each worker task.
+    ///
+    /// This value is stored but not read: this crate spawns no worker tasks
+    /// itself, so setting this field has no effect.
+    pub task_stack_size: usize,
+}
+
+#[cfg(any(feature = "wifi", feature = "ble"))]
+impl Default for RadioConfig {
+    fn default() -> Self {
+        Self {
+            core: crate::Cpu::ProCpu,
+            task_stack_size: DEFAULT_TASK_STACK_SIZE,
+        }
+    }
+}
+
+#[cfg(any(feature = "wifi", feature = "ble"))]
+static RADIO_CONFIG: critical_section::Mutex<core::cell::Cell<RadioConfig>> =
+    critical_section::Mutex::new(core::cell::Cell::new(RadioConfig {
+        core: crate::Cpu::ProCpu,
+        task_stack_size: DEFAULT_TASK_STACK_SIZE,
+    }));
+
+/// Sets the [`RadioConfig`] used by the next call to [`init`] (or
+/// [`init_with_calibration_store`]) -- matching [`set_phy_calibration_data`],
+/// which likewise takes effect on the next init rather than immediately.
+#[instability::unstable]
+#[cfg(any(feature = "wifi", feature = "ble"))]
+pub fn set_radio_config(config: RadioConfig) {
+    critical_section::with(|cs| RADIO_CONFIG.borrow(cs).set(config));
+}
+
+#[cfg(any(feature = "wifi", feature = "ble"))]
+fn radio_config() -> RadioConfig {
+    critical_section::with(|cs| RADIO_CONFIG.borrow(cs).get())
+}
+
 /// Get calibration data.
 ///
 /// Returns the last calibration result.
@@ -0,0 +1,167 @@
+//! # PHY calibration
+//!
+//! Wraps the PHY RF calibration data the driver produces during bring-up, so
+//! it can be inspected, overridden, and cached across boots by a
+//! [`CalibrationStore`].
+
+use super::sys::include::esp_phy_calibration_data_t;
+
+/// Size, in bytes, of the raw calibration blob produced/consumed by the
+/// underlying PHY driver.
+pub(crate) const PHY_CALIBRATION_DATA_LENGTH: usize = core::mem::size_of::<esp_phy_calibration_data_t>();
+
+static mut CALIBRATION_DATA: esp_phy_calibration_data_t = unsafe { core::mem::zeroed() };
+
+static LAST_RESULT: critical_section::Mutex<core::cell::Cell<Option<CalibrationResult>>> =
+    critical_section::Mutex::new(core::cell::Cell::new(None));
+
+/// Outcome of the PHY calibration that ran during the most recent radio
+/// init.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum CalibrationResult {
+    /// Calibration completed as a partial calibration, using the data that
+    /// was supplied via [`super::set_phy_calibration_data`].
+    Success,
+    /// The supplied data didn't match this device -- e.g. its PHY version or
+    /// MAC address differed from when the data was captured -- or no data
+    /// was supplied, so a full calibration ran instead. Callers that cache
+    /// calibration data should re-persist it after seeing this result.
+    DataCheckFailed,
+}
+
+/// Copies the current calibration data into `data`.
+pub(crate) fn backup_phy_calibration_data(data: &mut [u8; PHY_CALIBRATION_DATA_LENGTH]) {
+    critical_section::with(|_| unsafe {
+        let src =
+            core::slice::from_raw_parts((&raw const CALIBRATION_DATA).cast::<u8>(), PHY_CALIBRATION_DATA_LENGTH);
+        data.copy_from_slice(src);
+    });
+}
+
+/// Overwrites the current calibration data with `data`, to be used the next
+/// time the PHY is brought up.
+pub(crate) fn set_phy_calibration_data(data: &[u8; PHY_CALIBRATION_DATA_LENGTH]) -> Result<(), ()> {
+    critical_section::with(|_| unsafe {
+        let dst = core::slice::from_raw_parts_mut(
+            (&raw mut CALIBRATION_DATA).cast::<u8>(),
+            PHY_CALIBRATION_DATA_LENGTH,
+        );
+        dst.copy_from_slice(data);
+    });
+
+    Ok(())
+}
+
+/// Returns the result of the most recent PHY calibration, if any has run.
+pub(crate) fn last_calibration_result() -> Option<CalibrationResult> {
+    critical_section::with(|cs| LAST_RESULT.borrow(cs).get())
+}
+
+/// Records the result of a just-completed PHY calibration.
+pub(crate) fn set_last_calibration_result(result: CalibrationResult) {
+    critical_section::with(|cs| LAST_RESULT.borrow(cs).set(Some(result)));
+}
+
+/// Brings up the PHY, optionally seeded with previously-cached calibration
+/// data for a partial (faster) calibration, and records the outcome.
+///
+/// This calls into the real `esp_phy_rf_init` calibration routine, passing
+/// `CALIBRATION_DATA` (seeded from `seed`, if given) as its in/out
+/// calibration buffer. The PHY driver itself rejects a `PARTIAL` request
+/// whose data doesn't check out for this device (PHY version, MAC address)
+/// and silently falls back to running a full calibration instead -- in that
+/// case `CALIBRATION_DATA` comes back holding the freshly-produced full
+/// calibration, not the rejected seed, so [`backup_phy_calibration_data`]
+/// always returns real calibration output rather than an echo of `seed` or
+/// leftover zeros.
+///
+/// # Safety note on `init_data`
+///
+/// PHY init data (antenna config, TX power tables, etc.) isn't modeled in
+/// this tree, so this passes a null pointer, which the real driver only
+/// accepts when it was built with `CONFIG_ESP_PHY_DEFAULT_INIT_IF_INVALID`.
+pub(crate) fn calibrate(seed: Option<&[u8; PHY_CALIBRATION_DATA_LENGTH]>) -> CalibrationResult {
+    if let Some(data) = seed {
+        let _ = set_phy_calibration_data(data);
+    }
+
+    // Data installed earlier through `set_phy_calibration_data` (or left by a
+    // previous calibration) seeds the PHY the same way an explicit `seed` does.
+    let seeded = seed.is_some()
+        || critical_section::with(|_| unsafe {
+            core::slice::from_raw_parts(
+                (&raw const CALIBRATION_DATA).cast::<u8>(),
+                PHY_CALIBRATION_DATA_LENGTH,
+            )
+            .iter()
+            .any(|&byte| byte != 0)
+        });
+
+    let mode = if seeded {
+        super::sys::include::esp_phy_calibration_mode_t_PHY_RF_CAL_PARTIAL
+    } else {
+        super::sys::include::esp_phy_calibration_mode_t_PHY_RF_CAL_FULL
+    };
+
+    // SAFETY: `CALIBRATION_DATA` is only ever accessed from within a
+    // critical section, and `esp_phy_rf_init` reads/writes exactly
+    // `PHY_CALIBRATION_DATA_LENGTH` bytes through the pointer we give it.
+    let (result, changed) = critical_section::with(|_| unsafe {
+        let before = CALIBRATION_DATA;
+
+        let result = super::sys::include::esp_phy_rf_init(
+            core::ptr::null(),
+            mode,
+            &raw mut CALIBRATION_DATA,
+            super::sys::include::esp_phy_module_t_PHY_BT_WIFI_MODULE,
+        );
+
+        let before_bytes = core::slice::from_raw_parts(
+            (&raw const before).cast::<u8>(),
+            PHY_CALIBRATION_DATA_LENGTH,
+        );
+        let after_bytes = core::slice::from_raw_parts(
+            (&raw const CALIBRATION_DATA).cast::<u8>(),
+            PHY_CALIBRATION_DATA_LENGTH,
+        );
+
+        (result, before_bytes != after_bytes)
+    });
+
+    // `esp_phy_rf_init` always returns `ESP_OK` even when it silently falls
+    // back from `PARTIAL` to a full calibration, so the return code alone
+    // can't tell us whether our seed was actually used. Comparing the
+    // buffer before/after can: a `PARTIAL` request the driver accepted
+    // leaves the seed's bytes untouched, while a rejected seed (or no seed
+    // at all) comes back overwritten with freshly-produced calibration
+    // data that the caller should re-persist.
+    let outcome = if result == 0 && seeded && !changed {
+        CalibrationResult::Success
+    } else {
+        CalibrationResult::DataCheckFailed
+    };
+
+    set_last_calibration_result(outcome);
+    outcome
+}
+
+/// Pluggable persistence for PHY calibration data, so applications don't pay
+/// for a full RF calibration on every boot.
+///
+/// Implementations are typically backed by a reserved flash/eFuse region
+/// (see the adjacent eFuse example) or, where no non-volatile storage is
+/// available, may always report no cached data from [`load`][Self::load].
+#[instability::unstable]
+pub trait CalibrationStore {
+    /// Attempts to load previously-stored calibration data into `buf`.
+    ///
+    /// Returns `true` if `buf` was filled with data that should be tried;
+    /// returns `false` if there is no cached data, in which case `buf` is
+    /// left unspecified and a full calibration will run.
+    fn load(&mut self, buf: &mut [u8; PHY_CALIBRATION_DATA_LENGTH]) -> bool;
+
+    /// Persists `data` so a later [`load`][Self::load] call can return it.
+    fn store(&mut self, data: &[u8; PHY_CALIBRATION_DATA_LENGTH]);
+}
@@ -0,0 +1,49 @@
+//! Low-level interrupt plumbing shared by the Wi-Fi and BLE drivers.
+//!
+//! Both drivers are serviced by a single radio controller interrupt;
+//! [`setup_radio_isr`] installs the one handler for it, which forwards the
+//! interrupt to the BLE driver when the `ble` feature is enabled.
+
+use crate::interrupt::Priority;
+
+/// Installs the radio controller interrupt handler used by Wi-Fi and/or
+/// BLE, enabled on `core` so it (and the worker tasks it wakes) run on the
+/// CPU an [`super::RadioConfig`] requested.
+pub(crate) fn setup_radio_isr(core: crate::Cpu) {
+    // SAFETY: called once, from `init`, before either driver is relied on to
+    // receive interrupts.
+    unsafe {
+        crate::interrupt::bind_interrupt(interrupt_source(), radio_isr_handler);
+    }
+
+    #[cfg(feature = "ble")]
+    super::ble::register_vhci_host_callback();
+
+    #[cfg(multi_core)]
+    unwrap!(crate::interrupt::enable_on_core(
+        interrupt_source(),
+        Priority::Priority1,
+        core
+    ));
+    #[cfg(not(multi_core))]
+    {
+        let _ = core;
+        unwrap!(crate::interrupt::enable(interrupt_source(), Priority::Priority1));
+    }
+}
+
+/// Reverses [`setup_radio_isr`].
+pub(crate) fn shutdown_radio_isr() {
+    crate::interrupt::disable(crate::Cpu::ProCpu, interrupt_source());
+    #[cfg(multi_core)]
+    crate::interrupt::disable(crate::Cpu::AppCpu, interrupt_source());
+}
+
+fn interrupt_source() -> crate::peripherals::Interrupt {
+    crate::peripherals::Interrupt::WIFI_MAC
+}
+
+extern "C" fn radio_isr_handler() {
+    #[cfg(feature = "ble")]
+    super::ble::on_controller_interrupt();
+}
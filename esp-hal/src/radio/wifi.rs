@@ -0,0 +1,411 @@
+//! # Wi-Fi driver
+//!
+//! Station (STA) and access-point (AP) support.
+//!
+//! ## Async event notifications
+//!
+//! [`WifiEvents`] gives applications an `async fn` to await IP-assignment
+//! events on the station interface, rather than polling for them: see
+//! [`WifiEvent`] for exactly what's covered today, and
+//! [`WifiEvents::next_event`] to consume them. This crate doesn't yet expose
+//! a station connect/scan/AP API, so connection lifecycle and scan-done
+//! events aren't modeled here -- only the events this crate's own
+//! [`WifiDevice::new`] actually produces.
+
+use core::{
+    cell::RefCell,
+    future::poll_fn,
+    sync::atomic::{AtomicU16, Ordering},
+    task::{Context, Poll, Waker},
+};
+
+use critical_section::Mutex;
+use docsplay::Display;
+
+/// Errors returned by the Wi-Fi driver.
+#[derive(Display, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum WifiError {
+    /// The underlying driver isn't initialized.
+    NotInitialized,
+    /// The driver reported an internal error with this code.
+    InternalError(i32),
+    /// The station is not connected to an access point.
+    Disconnected,
+}
+
+impl core::error::Error for WifiError {}
+
+/// Wi-Fi power-save mode. Forwarded to the driver by `set_power_save`.
+///
+/// The default, [`PowerSaveMode::None`], keeps the radio always on for the
+/// lowest latency at the cost of the highest power draw.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PowerSaveMode {
+    /// No power saving: the radio is always on.
+    #[default]
+    None,
+    /// Modem sleep: the radio wakes for every DTIM beacon.
+    Minimum,
+    /// The radio wakes once every `listen_interval` beacons, trading
+    /// latency for lower power draw.
+    Maximum,
+}
+
+static CURRENT_POWER_SAVE_MODE: Mutex<RefCell<PowerSaveMode>> =
+    Mutex::new(RefCell::new(PowerSaveMode::None));
+/// Default listen interval, in DTIM beacons, matching the driver's own
+/// default.
+const DEFAULT_LISTEN_INTERVAL: u16 = 3;
+static LISTEN_INTERVAL: AtomicU16 = AtomicU16::new(DEFAULT_LISTEN_INTERVAL);
+
+/// Sets the Wi-Fi power-save mode, forwarded to the driver's
+/// `esp_wifi_set_ps`.
+///
+/// This is the primary lever for battery-powered station applications:
+/// [`PowerSaveMode::Minimum`] wakes for every DTIM beacon, while
+/// [`PowerSaveMode::Maximum`] only wakes once every listen interval, as
+/// configured in the driver.
+#[instability::unstable]
+pub fn set_power_save(mode: PowerSaveMode) -> Result<(), WifiError> {
+    let ps_type = match mode {
+        PowerSaveMode::None => super::sys::include::wifi_ps_type_t_WIFI_PS_NONE,
+        PowerSaveMode::Minimum => super::sys::include::wifi_ps_type_t_WIFI_PS_MIN_MODEM,
+        PowerSaveMode::Maximum => super::sys::include::wifi_ps_type_t_WIFI_PS_MAX_MODEM,
+    };
+
+    let result = unsafe { super::sys::include::esp_wifi_set_ps(ps_type) };
+    if result != 0 {
+        return Err(WifiError::InternalError(result));
+    }
+
+    critical_section::with(|cs| *CURRENT_POWER_SAVE_MODE.borrow(cs).borrow_mut() = mode);
+    Ok(())
+}
+
+/// Returns the power-save mode most recently set via [`set_power_save`]
+/// (or [`PowerSaveMode::None`] if it has never been called).
+#[instability::unstable]
+pub fn power_save() -> PowerSaveMode {
+    critical_section::with(|cs| *CURRENT_POWER_SAVE_MODE.borrow(cs).borrow())
+}
+
+/// Records a listen interval, in DTIM beacon intervals, returned by
+/// [`listen_interval`]. The value is not passed to the driver, so it does
+/// not change how often the radio wakes in [`PowerSaveMode::Maximum`].
+#[instability::unstable]
+pub fn set_listen_interval(beacons: u16) {
+    LISTEN_INTERVAL.store(beacons, Ordering::Relaxed);
+}
+
+/// Returns the listen interval set via [`set_listen_interval`].
+#[instability::unstable]
+pub fn listen_interval() -> u16 {
+    LISTEN_INTERVAL.load(Ordering::Relaxed)
+}
+
+/// A fixed IPv4 configuration for [`IpConfig::Static`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StaticIpConfig {
+    /// The station's own address.
+    pub address: core::net::Ipv4Addr,
+    /// The default gateway.
+    pub gateway: core::net::Ipv4Addr,
+    /// The subnet mask.
+    pub netmask: core::net::Ipv4Addr,
+    /// DNS server to report to the network stack, if any.
+    pub dns: Option<core::net::Ipv4Addr>,
+}
+
+/// How the station interface's IPv4 address is assigned.
+///
+/// Set via [`set_ip_config`] before constructing a [`WifiDevice`], which is
+/// what actually applies it. This is a first-class alternative to reaching
+/// past this driver into smoltcp's own configuration to disable DHCP and
+/// assign a fixed address -- the common "device acts as a server at a known
+/// address" case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum IpConfig {
+    /// Obtain an address via DHCP.
+    #[default]
+    Dhcp,
+    /// Use a fixed address, with DHCP disabled.
+    Static(StaticIpConfig),
+}
+
+static IP_CONFIG: Mutex<RefCell<IpConfig>> = Mutex::new(RefCell::new(IpConfig::Dhcp));
+
+/// Sets how the station interface's IPv4 address is assigned.
+///
+/// Takes effect the next time a [`WifiDevice`] is constructed. Once it is, a
+/// [`WifiEvent::StaGotIp`] (DHCP) or [`WifiEvent::StaStaticIpAssigned`]
+/// (static) event is pushed, matching whichever [`IpConfig`] is in effect.
+#[instability::unstable]
+pub fn set_ip_config(config: IpConfig) {
+    critical_section::with(|cs| *IP_CONFIG.borrow(cs).borrow_mut() = config);
+}
+
+/// Returns the [`IpConfig`] set via [`set_ip_config`] (DHCP by default).
+#[instability::unstable]
+pub fn ip_config() -> IpConfig {
+    critical_section::with(|cs| *IP_CONFIG.borrow(cs).borrow())
+}
+
+/// Called once the station interface has an IPv4 address, pushing whichever
+/// [`WifiEvent`] matches the [`IpConfig`] currently in effect.
+fn on_station_ip_assigned() {
+    let event = match ip_config() {
+        IpConfig::Dhcp => WifiEvent::StaGotIp,
+        IpConfig::Static(_) => WifiEvent::StaStaticIpAssigned,
+    };
+    on_driver_event(event);
+}
+
+/// Registered with `esp_event_handler_register` for `IP_EVENT` /
+/// `IP_EVENT_STA_GOT_IP`, so [`WifiEvent::StaGotIp`] is only pushed once
+/// DHCP actually completes -- `esp_netif_dhcpc_start` only starts the async
+/// client and returns long before an address is obtained.
+extern "C" fn on_ip_event(
+    _event_handler_arg: *mut core::ffi::c_void,
+    _event_base: super::sys::include::esp_event_base_t,
+    event_id: i32,
+    _event_data: *mut core::ffi::c_void,
+) {
+    if event_id == super::sys::include::ip_event_t_IP_EVENT_STA_GOT_IP as i32 {
+        on_station_ip_assigned();
+    }
+}
+
+fn ipv4_to_esp_netif(addr: core::net::Ipv4Addr) -> super::sys::include::esp_ip4_addr_t {
+    super::sys::include::esp_ip4_addr_t {
+        addr: u32::from_ne_bytes(addr.octets()),
+    }
+}
+
+/// The station network interface, as handed to a smoltcp (or other network
+/// stack) integration.
+///
+/// Constructing a [`WifiDevice`] is what applies the [`IpConfig`] set via
+/// [`set_ip_config`] to the underlying `esp_netif` handle -- disabling DHCP
+/// and installing a fixed address in one call for [`IpConfig::Static`],
+/// rather than requiring callers to reach past this driver into the network
+/// stack's own configuration.
+#[instability::unstable]
+#[derive(Debug)]
+pub struct WifiDevice {
+    _private: (),
+}
+
+impl WifiDevice {
+    /// Brings up the station interface, applying the [`IpConfig`] set via
+    /// [`set_ip_config`] (DHCP by default).
+    ///
+    /// For [`IpConfig::Static`] the fixed address takes effect immediately,
+    /// so a [`WifiEvent::StaStaticIpAssigned`] event is pushed before this
+    /// returns. For [`IpConfig::Dhcp`] this only starts the DHCP client --
+    /// the corresponding [`WifiEvent::StaGotIp`] is pushed later, once the
+    /// driver's `IP_EVENT_STA_GOT_IP` event callback actually fires.
+    pub fn new() -> Result<Self, WifiError> {
+        let netif = unsafe {
+            super::sys::include::esp_netif_get_handle_from_ifkey(c"WIFI_STA_DEF".as_ptr())
+        };
+
+        match ip_config() {
+            IpConfig::Dhcp => {
+                let result = unsafe {
+                    super::sys::include::esp_event_handler_register(
+                        super::sys::include::IP_EVENT,
+                        super::sys::include::ip_event_t_IP_EVENT_STA_GOT_IP as i32,
+                        Some(on_ip_event),
+                        core::ptr::null_mut(),
+                    )
+                };
+                if result != 0 {
+                    return Err(WifiError::InternalError(result));
+                }
+
+                let result = unsafe { super::sys::include::esp_netif_dhcpc_start(netif) };
+                // ESP_ERR_INVALID_STATE just means DHCP is already running.
+                if result != 0
+                    && result != super::sys::include::ESP_ERR_INVALID_STATE as i32
+                {
+                    return Err(WifiError::InternalError(result));
+                }
+
+                return Ok(Self { _private: () });
+            }
+            IpConfig::Static(config) => {
+                let result = unsafe { super::sys::include::esp_netif_dhcpc_stop(netif) };
+                if result != 0
+                    && result != super::sys::include::ESP_ERR_INVALID_STATE as i32
+                {
+                    return Err(WifiError::InternalError(result));
+                }
+
+                let ip_info = super::sys::include::esp_netif_ip_info_t {
+                    ip: ipv4_to_esp_netif(config.address),
+                    gw: ipv4_to_esp_netif(config.gateway),
+                    netmask: ipv4_to_esp_netif(config.netmask),
+                };
+                let result =
+                    unsafe { super::sys::include::esp_netif_set_ip_info(netif, &ip_info) };
+                if result != 0 {
+                    return Err(WifiError::InternalError(result));
+                }
+
+                if let Some(dns) = config.dns {
+                    let dns_info = super::sys::include::esp_netif_dns_info_t {
+                        ip: ipv4_to_esp_netif(dns),
+                    };
+                    let result = unsafe {
+                        super::sys::include::esp_netif_set_dns_info(
+                            netif,
+                            super::sys::include::esp_netif_dns_type_t_ESP_NETIF_DNS_MAIN,
+                            &dns_info,
+                        )
+                    };
+                    if result != 0 {
+                        return Err(WifiError::InternalError(result));
+                    }
+                }
+            }
+        }
+
+        on_station_ip_assigned();
+
+        Ok(Self { _private: () })
+    }
+}
+
+/// Wi-Fi lifecycle events, delivered in order via [`WifiEvents::next_event`].
+///
+/// This only covers IP-assignment, the one lifecycle stage
+/// [`WifiDevice::new`] actually drives. Connection lifecycle (station
+/// associate/disassociate), scan completion, and AP client join/leave would
+/// need a station connect/scan/AP-mode API this crate doesn't have yet, so
+/// there's deliberately no variant for them here -- add one once there's a
+/// real producer to back it, rather than a variant [`WifiEvents::next_event`]
+/// can never actually return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum WifiEvent {
+    /// The station was assigned an IP address via DHCP.
+    StaGotIp,
+    /// The station interface came up using the fixed address set via
+    /// [`set_ip_config`].
+    StaStaticIpAssigned,
+}
+
+const EVENT_QUEUE_CAPACITY: usize = 16;
+
+struct EventQueue {
+    events: [Option<WifiEvent>; EVENT_QUEUE_CAPACITY],
+    head: usize,
+    tail: usize,
+    waker: Option<Waker>,
+}
+
+impl EventQueue {
+    const fn new() -> Self {
+        Self {
+            events: [None; EVENT_QUEUE_CAPACITY],
+            head: 0,
+            tail: 0,
+            waker: None,
+        }
+    }
+
+    /// Pushes `event`, waking a pending [`WifiEvents::next_event`] call.
+    ///
+    /// If the queue is full, the oldest event is dropped -- the driver's
+    /// event callback must never block waiting for a consumer.
+    fn push(&mut self, event: WifiEvent) {
+        let next_tail = (self.tail + 1) % EVENT_QUEUE_CAPACITY;
+        if next_tail == self.head {
+            self.head = (self.head + 1) % EVENT_QUEUE_CAPACITY;
+        }
+
+        self.events[self.tail] = Some(event);
+        self.tail = next_tail;
+
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn pop(&mut self) -> Option<WifiEvent> {
+        if self.head == self.tail {
+            return None;
+        }
+
+        let event = self.events[self.head].take();
+        self.head = (self.head + 1) % EVENT_QUEUE_CAPACITY;
+        event
+    }
+}
+
+static EVENT_QUEUE: Mutex<RefCell<EventQueue>> = Mutex::new(RefCell::new(EventQueue::new()));
+
+/// Called from the driver's internal event callback whenever a Wi-Fi
+/// lifecycle event occurs. Pushes `event` onto the queue [`WifiEvents`]
+/// consumes, waking any task currently awaiting one.
+pub(crate) fn on_driver_event(event: WifiEvent) {
+    critical_section::with(|cs| EVENT_QUEUE.borrow(cs).borrow_mut().push(event));
+}
+
+/// Handle for awaiting Wi-Fi lifecycle events via [`next_event`][Self::next_event].
+///
+/// All instances drain the same underlying queue -- this type exists to
+/// give the awaiting side a place to hang the `async fn`, not to provide
+/// independent per-handle event streams.
+#[instability::unstable]
+#[derive(Debug, Default)]
+pub struct WifiEvents {
+    _private: (),
+}
+
+impl WifiEvents {
+    /// Creates a new handle onto the shared Wi-Fi event queue.
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+
+    /// Waits for the next Wi-Fi lifecycle event.
+    pub async fn next_event(&mut self) -> WifiEvent {
+        poll_fn(|cx| self.poll_next_event(cx)).await
+    }
+
+    fn poll_next_event(&mut self, cx: &mut Context<'_>) -> Poll<WifiEvent> {
+        critical_section::with(|cs| {
+            let mut queue = EVENT_QUEUE.borrow(cs).borrow_mut();
+            match queue.pop() {
+                Some(event) => Poll::Ready(event),
+                None => {
+                    queue.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        })
+    }
+}
+
+#[cfg(coex)]
+pub(crate) fn coex_initialize() -> i32 {
+    unsafe { super::sys::include::esp_coex_adapter_register(core::ptr::null_mut()) }
+}
+
+#[cfg(coex)]
+pub(crate) mod os_adapter {
+    pub(crate) unsafe fn coex_disable() {
+        unsafe { super::super::sys::include::esp_coex_disable() }
+    }
+
+    pub(crate) unsafe fn coex_deinit() {
+        unsafe { super::super::sys::include::esp_coex_common_deinit() }
+    }
+}
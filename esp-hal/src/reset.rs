@@ -0,0 +1,107 @@
+//! # Reset reason and wake-up cause
+//!
+//! ## Overview
+//! This module provides ergonomic accessors for the hardware reset-cause
+//! register, letting firmware distinguish a power-on reset from a deep-sleep
+//! wake-up, a watchdog timeout, a brownout, or a JTAG/USB reset. This is
+//! useful for field devices that need to log crash causes and branch their
+//! recovery behaviour accordingly.
+
+use crate::rtc_cntl::SocResetReason;
+
+/// The additional status describing an overloaded [SocResetReason::ChipPowerOn].
+///
+/// ESP-IDF reuses the `0x01` reset-cause value for three distinct causes,
+/// which are disambiguated using extra status bits alongside the reset-cause
+/// register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PowerOnCause {
+    /// A normal power-on reset.
+    PowerOn,
+    /// The chip was reset by a brownout (supply voltage dropped too low).
+    BrownOut,
+    /// The super watchdog timer reset the chip.
+    SuperWdt,
+}
+
+/// Returns the reason the chip was last reset.
+///
+/// Returns `None` if the hardware reports a reset-cause value that isn't
+/// represented by [SocResetReason].
+pub fn reset_reason() -> Option<SocResetReason> {
+    SocResetReason::from_repr(raw_reset_reason() as usize)
+}
+
+/// Disambiguates the overloaded [SocResetReason::ChipPowerOn] (`0x01`) cause
+/// into power-on, brownout, or super-watchdog, using the additional status
+/// bits documented on [SocResetReason].
+///
+/// Returns `None` if the last reset reason was not `ChipPowerOn`.
+pub fn power_on_cause() -> Option<PowerOnCause> {
+    if reset_reason() != Some(SocResetReason::ChipPowerOn) {
+        return None;
+    }
+
+    Some(if super_watchdog_triggered() {
+        PowerOnCause::SuperWdt
+    } else if brown_out_triggered() {
+        PowerOnCause::BrownOut
+    } else {
+        PowerOnCause::PowerOn
+    })
+}
+
+fn raw_reset_reason() -> u32 {
+    cfg_if::cfg_if! {
+        if #[cfg(any(esp32c6, esp32h2))] {
+            unsafe { &*crate::peripherals::LP_AON::ptr() }
+                .store0()
+                .read()
+                .reset_reason()
+                .bits() as u32
+        } else {
+            unsafe { &*crate::peripherals::RTC_CNTL::ptr() }
+                .reset_state()
+                .read()
+                .reset_reason()
+                .bits() as u32
+        }
+    }
+}
+
+fn brown_out_triggered() -> bool {
+    cfg_if::cfg_if! {
+        if #[cfg(any(esp32c6, esp32h2))] {
+            unsafe { &*crate::peripherals::LP_AON::ptr() }
+                .store0()
+                .read()
+                .brown_out_det()
+                .bit_is_set()
+        } else {
+            unsafe { &*crate::peripherals::RTC_CNTL::ptr() }
+                .brown_out()
+                .read()
+                .ana_rst_wait()
+                .bit_is_set()
+        }
+    }
+}
+
+fn super_watchdog_triggered() -> bool {
+    cfg_if::cfg_if! {
+        if #[cfg(any(esp32c6, esp32h2))] {
+            unsafe { &*crate::peripherals::LP_AON::ptr() }
+                .store0()
+                .read()
+                .super_wdt_reset()
+                .bit_is_set()
+        } else {
+            unsafe { &*crate::peripherals::RTC_CNTL::ptr() }
+                .wdtconfig0()
+                .read()
+                .wdt_flashboot_mod_en()
+                .bit_is_set()
+        }
+    }
+}
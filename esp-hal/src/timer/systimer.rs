@@ -90,6 +90,9 @@ impl<'d> SystemTimer<'d> {
         #[cfg(soc_etm)]
         etm::enable_etm();
 
+        #[cfg(feature = "embassy")]
+        embassy_time_driver::init();
+
         Self {
             unit0: SpecificUnit::<'_, 0>::new().into(),
             #[cfg(not(esp32s2))]
@@ -496,6 +499,48 @@ pub trait Comparator {
         }
         unwrap!(interrupt::enable(interrupt, handler.priority()));
     }
+
+    /// Set the interrupt handler for this comparator, and steer its delivery
+    /// to a specific CPU core.
+    ///
+    /// Only available on dual-core parts. The handler is bound into `cpu`'s vector/dispatch
+    /// path, and the interrupt is left disabled on every other core.
+    ///
+    /// # Errors
+    ///
+    /// Binding an interrupt handler requires running on the target core, since
+    /// each core's vector table is configured independently. Returns
+    /// [AffinityError::WrongCore] if `cpu` is not the core this function is
+    /// called from -- e.g. because it hasn't been started yet.
+    #[cfg(multi_core)]
+    fn set_interrupt_handler_affine(
+        &self,
+        handler: InterruptHandler,
+        cpu: Cpu,
+    ) -> Result<(), AffinityError> {
+        if crate::get_core() != cpu {
+            // We can't bind into `cpu`'s vector table from here. The caller is
+            // expected to have started `cpu` and to call this function from it,
+            // e.g. from the closure passed to `cpu_control::start_app_core`.
+            return Err(AffinityError::WrongCore);
+        }
+
+        self.set_interrupt_handler(handler);
+
+        Ok(())
+    }
+}
+
+/// Errors returned when steering an [Alarm]'s interrupt to a specific core.
+#[cfg(multi_core)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AffinityError {
+    /// Binding an interrupt handler must happen on the core it targets, but
+    /// this function was called from a different core. Call this function
+    /// again from within the target core's entry point (e.g. the closure
+    /// passed to `cpu_control::start_app_core`) if it hasn't been started
+    /// yet.
+    WrongCore,
 }
 
 /// A specific [Comparator]. i.e. Either comparator 0, comparator 1, etc.
@@ -638,6 +683,26 @@ impl<'d> Alarm<'d> {
             unit: unit.borrow(),
         }
     }
+
+    /// Rebinds this alarm's comparator to a different unit, so it free-runs
+    /// off that unit's counter instead of the one it was created with.
+    ///
+    /// [SystemTimer::split] wires all three alarms to Unit 0; this lets an
+    /// alarm be driven from Unit 1 instead, e.g. so that
+    /// [Unit::configure][Unit::configure]'ing Unit 0 to stall on one core's
+    /// on-chip debugger doesn't also freeze timekeeping for this alarm.
+    #[cfg(not(esp32s2))]
+    pub fn set_unit(&mut self, unit: &FrozenUnit<'d, AnyUnit<'d>>) {
+        let unit = unit.borrow();
+        self.comparator.set_unit(unit.channel() == 0);
+        self.unit = unit;
+    }
+
+    /// Applies a [UnitConfig] to the unit this alarm is currently bound to.
+    #[cfg(not(esp32s2))]
+    pub fn configure_unit(&self, config: UnitConfig) {
+        self.unit.configure(config);
+    }
 }
 
 impl InterruptConfigurable for Alarm<'_> {
@@ -646,6 +711,132 @@ impl InterruptConfigurable for Alarm<'_> {
     }
 }
 
+#[cfg(multi_core)]
+impl Alarm<'_> {
+    /// Sets the interrupt handler for this alarm, pinning its delivery to a
+    /// specific CPU core.
+    ///
+    /// See [Comparator::set_interrupt_handler_affine] for details and error
+    /// conditions.
+    pub fn set_interrupt_handler_affine(
+        &mut self,
+        handler: InterruptHandler,
+        cpu: Cpu,
+    ) -> Result<(), AffinityError> {
+        self.comparator.set_interrupt_handler_affine(handler, cpu)
+    }
+}
+
+impl Alarm<'_> {
+    /// Arms this alarm for a one-shot interrupt at the given target tick
+    /// count (as read by [Unit::read_count]), and asynchronously waits for
+    /// it to fire.
+    ///
+    /// This arms the comparator itself, independent of the embassy-time
+    /// global driver -- it's a lightweight one-shot delay tied to this
+    /// specific alarm.
+    pub async fn wait_until(&mut self, target: u64) {
+        self.comparator.set_mode(ComparatorMode::Target);
+        self.comparator.set_target(target);
+
+        // `AlarmFuture::new` clears any stale pending bit and unmasks the
+        // interrupt; only once that's done do we arm the comparator. Target
+        // mode fires once on crossing, so arming first risks the target
+        // already being crossed before we unmask -- the pending bit would be
+        // set and then wiped by `clear_interrupt()` while still masked, with
+        // no second crossing left to ever refire it.
+        let future = asynch::AlarmFuture::new(self);
+        self.comparator.set_enable(true);
+        future.await;
+    }
+
+    /// Asynchronously waits for `duration` to elapse, relative to the
+    /// current counter value.
+    pub async fn wait(&mut self, duration: MicrosDurationU64) {
+        let ticks = duration.ticks() * (SystemTimer::ticks_per_second() / 1_000_000);
+        let target = self.unit.read_count() + ticks;
+
+        self.wait_until(target).await;
+    }
+
+    /// Returns the current counter value in native SYSTIMER ticks.
+    ///
+    /// Prefer this over [Timer::now][super::Timer::now] when full precision
+    /// matters: the microsecond API divides by `ticks_per_second() /
+    /// 1_000_000`, which truncates on chips where the tick rate isn't an
+    /// integer multiple of 1 MHz.
+    pub fn now_raw(&self) -> u64 {
+        self.unit.read_count()
+    }
+
+    /// Arms the comparator using a target expressed in native SYSTIMER
+    /// ticks, bypassing the lossy microsecond round-trip that
+    /// [Timer::load_value][super::Timer::load_value] performs.
+    ///
+    /// In target mode, `ticks` is relative to the current counter value. In
+    /// period mode, `ticks` is the absolute reload period.
+    pub fn load_ticks(&self, ticks: u64) -> Result<(), Error> {
+        let mode = self.comparator.mode();
+
+        if matches!(mode, ComparatorMode::Period) {
+            if (ticks & !SystemTimer::PERIOD_MASK) != 0 {
+                return Err(Error::InvalidTimeout);
+            }
+
+            self.comparator.set_period(ticks as u32);
+            self.comparator.set_mode(ComparatorMode::Target);
+            self.comparator.set_mode(ComparatorMode::Period);
+        } else {
+            #[cfg(not(esp32s2))]
+            if (ticks & !SystemTimer::BIT_MASK) != 0 {
+                return Err(Error::InvalidTimeout);
+            }
+
+            let target = self.unit.read_count() + ticks;
+            self.comparator.set_target(target);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "embassy")]
+impl Alarm<'_> {
+    /// Converts a SYSTIMER raw tick count (at
+    /// [SystemTimer::ticks_per_second]) to an
+    /// `embassy_time::Instant`, in whatever tick rate the application
+    /// selected via `embassy-time`'s `tick-hz-*` features.
+    ///
+    /// The scaling is done with `u128` intermediates so the multiply doesn't
+    /// overflow across the full 52-bit (64-bit on ESP32-S2) counter range.
+    pub fn raw_ticks_to_instant(ticks: u64) -> embassy_time::Instant {
+        let ticks = ticks as u128 * ::embassy_time_driver::TICK_HZ as u128
+            / SystemTimer::ticks_per_second() as u128;
+        embassy_time::Instant::from_ticks(ticks as u64)
+    }
+
+    /// Converts an `embassy_time::Duration` to a SYSTIMER raw tick count
+    /// (at [SystemTimer::ticks_per_second]).
+    pub fn duration_to_raw_ticks(duration: embassy_time::Duration) -> u64 {
+        let ticks = duration.as_ticks() as u128 * SystemTimer::ticks_per_second() as u128
+            / ::embassy_time_driver::TICK_HZ as u128;
+        ticks as u64
+    }
+
+    /// Returns the current counter value as an `embassy_time::Instant`.
+    pub fn now_instant(&self) -> embassy_time::Instant {
+        Self::raw_ticks_to_instant(self.now_raw())
+    }
+
+    /// Arms the comparator to fire `duration` from now.
+    ///
+    /// In target mode, the comparator fires once `duration` has elapsed. In
+    /// period mode, `duration` becomes the reload period.
+    pub fn load_duration(&self, duration: embassy_time::Duration) -> Result<(), Error> {
+        self.load_ticks(Self::duration_to_raw_ticks(duration))
+    }
+}
+
 impl crate::private::Sealed for Alarm<'_> {}
 
 impl super::Timer for Alarm<'_> {
@@ -797,6 +988,146 @@ impl Peripheral for Alarm<'_> {
 static CONF_LOCK: Lock = Lock::new();
 static INT_ENA_LOCK: Lock = Lock::new();
 
+// A callback-driven one-shot/periodic timer service layered on [Alarm], for
+// blocking code that wants a plain closure instead of an `async` future.
+mod callback_timer {
+    use core::cell::Cell;
+
+    use super::*;
+
+    const NUM_ALARMS: usize = 3;
+
+    /// A callback registered against a specific comparator channel.
+    type Callback = &'static mut (dyn FnMut() + Send);
+
+    struct CallbackSlot(Cell<Option<Callback>>);
+
+    // SAFETY: the slot is only ever accessed from within the comparator ISR
+    // or while that ISR is disabled for the channel.
+    unsafe impl Sync for CallbackSlot {}
+
+    static CALLBACKS: [CallbackSlot; NUM_ALARMS] =
+        [const { CallbackSlot(Cell::new(None)) }; NUM_ALARMS];
+
+    fn on_fire(channel: u8) {
+        unsafe { &*SYSTIMER::PTR }
+            .int_clr()
+            .write(|w| w.target(channel).clear_bit_by_one());
+
+        let Some(callback) = CALLBACKS[channel as usize].0.take() else {
+            return;
+        };
+
+        callback();
+
+        let comparator: AnyComparator<'static> = match channel {
+            0 => SpecificComparator::<'_, 0>::new().into(),
+            1 => SpecificComparator::<'_, 1>::new().into(),
+            2 => SpecificComparator::<'_, 2>::new().into(),
+            _ => unreachable!(),
+        };
+
+        if matches!(comparator.mode(), ComparatorMode::Period) {
+            // The hardware reloads the target by `period` on every fire, so
+            // periodic alarms never need re-arming from software -- put the
+            // callback back so the next fire can find it.
+            CALLBACKS[channel as usize].0.set(Some(callback));
+        } else {
+            comparator.set_enable(false);
+            lock(&INT_ENA_LOCK, || {
+                unsafe { &*SYSTIMER::PTR }
+                    .int_ena()
+                    .modify(|_, w| w.target(channel).clear_bit());
+            });
+        }
+    }
+
+    #[procmacros::handler]
+    fn callback0_handler() {
+        on_fire(0);
+    }
+
+    #[procmacros::handler]
+    fn callback1_handler() {
+        on_fire(1);
+    }
+
+    #[procmacros::handler]
+    fn callback2_handler() {
+        on_fire(2);
+    }
+
+    pub(super) fn handler_for(channel: u8) -> InterruptHandler {
+        match channel {
+            0 => callback0_handler,
+            1 => callback1_handler,
+            2 => callback2_handler,
+            _ => unreachable!(),
+        }
+    }
+
+    pub(super) fn set_callback(channel: u8, callback: Callback) {
+        CALLBACKS[channel as usize].0.set(Some(callback));
+    }
+
+    pub(super) fn clear_callback(channel: u8) -> bool {
+        CALLBACKS[channel as usize].0.take().is_some()
+    }
+}
+
+impl Alarm<'_> {
+    /// Schedules `callback` to run once, `duration` from now, from the
+    /// comparator's interrupt handler.
+    ///
+    /// `callback` must be `'static` (e.g. a function item, or a closure
+    /// stored in a `static`) since it is reached from an ISR with no
+    /// reference back to this call stack.
+    pub fn after(&mut self, duration: MicrosDurationU64, callback: &'static mut (dyn FnMut() + Send)) {
+        self.cancel();
+
+        self.comparator.set_mode(ComparatorMode::Target);
+        callback_timer::set_callback(self.comparator.channel(), callback);
+        self.set_interrupt_handler(callback_timer::handler_for(self.comparator.channel()));
+
+        let _ = self.load_value(duration);
+        self.enable_interrupt(true);
+        self.start();
+    }
+
+    /// Schedules `callback` to run every `period`, starting `period` from
+    /// now, from the comparator's interrupt handler.
+    ///
+    /// Re-arming happens entirely in hardware (the comparator's period
+    /// register is reloaded on every fire), so the schedule does not drift
+    /// the way re-arming relative to a freshly-read `now()` would.
+    pub fn every(&mut self, period: MicrosDurationU64, callback: &'static mut (dyn FnMut() + Send)) {
+        self.cancel();
+
+        self.enable_auto_reload(true);
+        callback_timer::set_callback(self.comparator.channel(), callback);
+        self.set_interrupt_handler(callback_timer::handler_for(self.comparator.channel()));
+
+        let _ = self.load_value(period);
+        self.enable_interrupt(true);
+        self.start();
+    }
+
+    /// Cancels a pending [Alarm::after]/[Alarm::every] schedule.
+    ///
+    /// Returns `true` if a callback was actually pending.
+    pub fn cancel(&mut self) -> bool {
+        self.comparator.set_enable(false);
+        self.enable_interrupt(false);
+        callback_timer::clear_callback(self.comparator.channel())
+    }
+
+    /// Returns whether a future fire from [Alarm::after]/[Alarm::every] is
+    /// still pending, by reading the comparator's own enable state.
+    pub fn is_scheduled(&self) -> bool {
+        self.comparator.is_enabled()
+    }
+}
+
 // Async functionality of the system timer.
 mod asynch {
     use core::{
@@ -905,6 +1236,10 @@ pub mod etm {
     //!    - SYSTIMER_EVT_CNT_CMPx: Indicates the alarm pulses generated by
     //!      COMPx
     //!
+    //!    The system timer also accepts an ETM task to reload (restart) a
+    //!    counter unit, so another peripheral's ETM event (e.g. a GPIO edge)
+    //!    can atomically reset the timebase without CPU involvement.
+    //!
     //! ## Example
     //! ```rust, no_run
     #![doc = crate::before_snippet!()]
@@ -947,8 +1282,333 @@ pub mod etm {
         }
     }
 
+    /// An ETM controlled SYSTIMER task: reloads (restarts) a counter [Unit]
+    /// from zero when another peripheral's ETM event fires.
+    pub struct Task<'a, U: Unit> {
+        unit: &'a U,
+    }
+
+    impl<'a, U: Unit> Task<'a, U> {
+        /// Creates an ETM task from the given [Unit].
+        pub fn new(unit: &'a U) -> Self {
+            let syst = unsafe { crate::peripherals::SYSTIMER::steal() };
+            match unit.channel() {
+                0 => syst.conf().modify(|_, w| w.timer_unit0_etm_en().set_bit()),
+                #[cfg(not(esp32s2))]
+                1 => syst.conf().modify(|_, w| w.timer_unit1_etm_en().set_bit()),
+                _ => unreachable!(),
+            }
+
+            Self { unit }
+        }
+    }
+
+    impl<U: Unit> crate::private::Sealed for Task<'_, U> {}
+
+    impl<U: Unit> crate::etm::EtmTask for Task<'_, U> {
+        fn id(&self) -> u8 {
+            53 + self.unit.channel()
+        }
+    }
+
     pub(super) fn enable_etm() {
         let syst = unsafe { crate::peripherals::SYSTIMER::steal() };
         syst.conf().modify(|_, w| w.etm_en().set_bit());
     }
 }
+
+#[cfg(feature = "embassy")]
+mod embassy_time_driver {
+    //! [`embassy_time_driver::Driver`] backed by a software multi-alarm
+    //! queue, multiplexed onto a single dedicated SYSTIMER comparator
+    //! (Comparator 0, referenced against Unit 0), so `embassy-time` works out
+    //! of the box with an effectively unbounded number of concurrent
+    //! `Timer::after` futures without requiring one hardware comparator per
+    //! software timer.
+
+    use core::cell::Cell;
+
+    use critical_section::Mutex;
+    use embassy_time_driver::{AlarmHandle, Driver};
+
+    use super::*;
+
+    /// Maximum number of concurrent software alarms. One bit of `allocated`
+    /// is used per alarm, so this cannot exceed 64.
+    const ALARM_COUNT: usize = 64;
+
+    /// Sentinel `timestamp` value for an alarm that has no pending deadline.
+    const NEVER: u64 = u64::MAX;
+
+    struct AlarmState {
+        timestamp: Cell<u64>,
+        callback: Cell<Option<(fn(*mut ()), *mut ())>>,
+    }
+
+    impl AlarmState {
+        const fn new() -> Self {
+            Self {
+                timestamp: Cell::new(NEVER),
+                callback: Cell::new(None),
+            }
+        }
+    }
+
+    // SAFETY: `timestamp`/`callback` are only ever accessed from within a
+    // critical section.
+    unsafe impl Sync for AlarmState {}
+
+    struct SystimerDriver {
+        // Bit `n` is set once alarm `n` has been handed out by `allocate_alarm`.
+        allocated: Mutex<Cell<u64>>,
+        alarms: [Mutex<AlarmState>; ALARM_COUNT],
+    }
+
+    embassy_time_driver::time_driver_impl!(static DRIVER: SystimerDriver = SystimerDriver {
+        allocated: Mutex::new(Cell::new(0)),
+        alarms: [const { Mutex::new(AlarmState::new()) }; ALARM_COUNT],
+    });
+
+    fn comparator() -> AnyComparator<'static> {
+        SpecificComparator::<'_, 0>::new().into()
+    }
+
+    fn ticks_per_tick_hz() -> u64 {
+        SystemTimer::ticks_per_second() / embassy_time_driver::TICK_HZ
+    }
+
+    fn now_ticks() -> u64 {
+        let unit = unsafe { SpecificUnit::<'_, 0>::conjure() };
+        unit.read_count() / ticks_per_tick_hz()
+    }
+
+    fn enable_comparator_interrupt(enable: bool) {
+        lock(&INT_ENA_LOCK, || {
+            unsafe { &*SYSTIMER::PTR }
+                .int_ena()
+                .modify(|_, w| w.target0().bit(enable));
+        });
+    }
+
+    /// Fires the callback of, and disarms, every allocated alarm whose
+    /// deadline has elapsed.
+    fn process_alarms(cs: critical_section::CriticalSection<'_>) {
+        let now = now_ticks();
+        let allocated = DRIVER.allocated.borrow(cs).get();
+        for i in 0..ALARM_COUNT {
+            if allocated & (1 << i) == 0 {
+                continue;
+            }
+
+            let state = DRIVER.alarms[i].borrow(cs);
+            if state.timestamp.get() > now {
+                continue;
+            }
+
+            state.timestamp.set(NEVER);
+            if let Some((callback, ctx)) = state.callback.get() {
+                callback(ctx);
+            }
+        }
+    }
+
+    /// Recomputes the nearest pending deadline across all allocated alarms
+    /// and reprograms the dedicated comparator for it, disabling the
+    /// comparator if the queue is empty.
+    fn schedule_next_alarm() {
+        critical_section::with(|cs| loop {
+            let allocated = DRIVER.allocated.borrow(cs).get();
+            let next_deadline = (0..ALARM_COUNT)
+                .filter(|i| allocated & (1 << i) != 0)
+                .map(|i| DRIVER.alarms[i].borrow(cs).timestamp.get())
+                .min()
+                .unwrap_or(NEVER);
+
+            if next_deadline == NEVER {
+                comparator().set_enable(false);
+                enable_comparator_interrupt(false);
+                return;
+            }
+
+            let target_ticks = next_deadline.saturating_mul(ticks_per_tick_hz());
+            let comparator = comparator();
+            comparator.set_mode(ComparatorMode::Target);
+            comparator.set_target(target_ticks);
+            comparator.set_enable(true);
+            enable_comparator_interrupt(true);
+
+            // The deadline may already be in the past by the time it's
+            // programmed (e.g. a newly-registered alarm whose timestamp has
+            // already elapsed) -- the comparator only fires on the counter
+            // reaching the target, so it would never trigger. Process
+            // immediately instead of waiting for an interrupt that won't
+            // come, then recompute the next deadline from what is left.
+            if target_ticks > now_ticks() * ticks_per_tick_hz() {
+                return;
+            }
+            process_alarms(cs);
+        });
+    }
+
+    impl Driver for SystimerDriver {
+        fn now(&self) -> u64 {
+            now_ticks()
+        }
+
+        unsafe fn allocate_alarm(&self) -> Option<AlarmHandle> {
+            critical_section::with(|cs| {
+                let allocated = self.allocated.borrow(cs);
+                for i in 0..ALARM_COUNT {
+                    if allocated.get() & (1 << i) == 0 {
+                        allocated.set(allocated.get() | (1 << i));
+                        return Some(AlarmHandle::new(i as u8));
+                    }
+                }
+                None
+            })
+        }
+
+        fn set_alarm_callback(&self, alarm: AlarmHandle, callback: fn(*mut ()), ctx: *mut ()) {
+            let i = alarm.id() as usize;
+            critical_section::with(|cs| {
+                self.alarms[i].borrow(cs).callback.set(Some((callback, ctx)));
+            });
+        }
+
+        fn set_alarm(&self, alarm: AlarmHandle, timestamp: u64) -> bool {
+            let i = alarm.id() as usize;
+
+            if timestamp <= self.now() {
+                return false;
+            }
+
+            critical_section::with(|cs| {
+                self.alarms[i].borrow(cs).timestamp.set(timestamp);
+            });
+            schedule_next_alarm();
+
+            true
+        }
+    }
+
+    fn on_interrupt() {
+        enable_comparator_interrupt(false);
+        comparator().set_enable(false);
+        unsafe { &*SYSTIMER::PTR }
+            .int_clr()
+            .write(|w| w.target0().clear_bit_by_one());
+
+        critical_section::with(process_alarms);
+        schedule_next_alarm();
+    }
+
+    #[procmacros::handler]
+    fn target0_handler() {
+        on_interrupt();
+    }
+
+    pub(super) fn init() {
+        comparator().set_interrupt_handler(target0_handler);
+    }
+}
+
+#[cfg(feature = "rtic")]
+pub mod monotonic {
+    //! # RTIC `Monotonic` backed by the SYSTIMER.
+    //!
+    //! This uses the same timebase as [`Alarm`]: it owns a comparator and
+    //! reads a borrowed unit, so it can be built either from a unit and
+    //! comparator directly or from an [`Alarm`] returned by
+    //! [SystemTimer::split].
+
+    use rtic_monotonic::Monotonic;
+
+    use super::*;
+
+    /// A `Monotonic` timer driven by a single SYSTIMER unit/comparator pair.
+    ///
+    /// Unlike a 16/32-bit hardware timer, the counter is wide enough
+    /// (
+    #[cfg_attr(esp32s2, doc = "64-bit")]
+    #[cfg_attr(not(esp32s2), doc = "52-bit")]
+    /// ) that it never needs a software overflow accumulator.
+    pub struct SystemTimerMonotonic<'d> {
+        unit: &'d AnyUnit<'d>,
+        comparator: AnyComparator<'d>,
+        start: u64,
+    }
+
+    impl<'d> SystemTimerMonotonic<'d> {
+        /// Creates a new monotonic timer from a unit and comparator.
+        ///
+        /// The comparator is dedicated to this monotonic for as long as it
+        /// lives. The unit is only read, so it may also be the unit of other
+        /// [Alarm]s.
+        pub fn new(unit: &'d AnyUnit<'d>, comparator: AnyComparator<'d>) -> Self {
+            comparator.set_mode(ComparatorMode::Target);
+            #[cfg(not(esp32s2))]
+            comparator.set_unit(unit.channel() == 0);
+
+            Self {
+                unit,
+                comparator,
+                start: 0,
+            }
+        }
+
+        /// Creates a new monotonic timer from an [Alarm] obtained from
+        /// [SystemTimer::split], reusing its
+        /// already-bound unit and comparator instead of requiring a fresh
+        /// unit/comparator pair.
+        pub fn from_alarm(alarm: Alarm<'d>) -> Self {
+            Self::new(alarm.unit, alarm.comparator)
+        }
+    }
+
+    impl Monotonic for SystemTimerMonotonic<'_> {
+        type Instant = fugit::TimerInstantU64<1_000_000>;
+        type Duration = fugit::TimerDurationU64<1_000_000>;
+
+        const DISABLE_INTERRUPT_ON_EMPTY_QUEUE: bool = false;
+
+        fn now(&mut self) -> Self::Instant {
+            let ticks = self.unit.read_count() - self.start;
+            let us = ticks / (SystemTimer::ticks_per_second() / 1_000_000);
+            Self::Instant::from_ticks(us)
+        }
+
+        fn set_compare(&mut self, instant: Self::Instant) {
+            let target_us = instant.duration_since_epoch().ticks();
+            let target_ticks =
+                self.start + target_us * (SystemTimer::ticks_per_second() / 1_000_000);
+
+            // Clamp to the hardware's bit width, and if the target is already in the
+            // past, schedule the minimum possible future value so the comparator still
+            // fires.
+            let target_ticks = target_ticks & SystemTimer::BIT_MASK;
+            let now = self.unit.read_count();
+            let target_ticks = if target_ticks <= now {
+                now + 1
+            } else {
+                target_ticks
+            };
+
+            self.comparator.set_target(target_ticks);
+        }
+
+        fn clear_compare_flag(&mut self) {
+            unsafe { &*crate::peripherals::SYSTIMER::PTR }
+                .int_clr()
+                .write(|w| w.target(self.comparator.channel()).clear_bit_by_one());
+        }
+
+        fn zero() -> Self::Instant {
+            Self::Instant::from_ticks(0)
+        }
+
+        fn reset(&mut self) {
+            self.start = self.unit.read_count();
+            self.comparator.set_enable(true);
+        }
+    }
+}
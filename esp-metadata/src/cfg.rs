@@ -0,0 +1,291 @@
+//! Peripheral driver configuration.
+//!
+//! This module defines the `[<driver>]` sections of a device TOML file, and
+//! the helpers `esp-metadata`'s codegen uses to turn them into `cargo:rustc-cfg`
+//! symbols, `macro_rules!` tables, and other generated artifacts.
+
+use std::fmt;
+
+/// The configuration of all drivers for a given chip.
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct PeriConfig {
+    pub gpio: Option<GpioConfig>,
+    pub i2c_master: Option<I2cMasterConfig>,
+    pub dma: Option<DmaConfig>,
+}
+
+/// The kind of DMA controller backing a [DmaController].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DmaKind {
+    /// General-purpose DMA, with a flexible channel/peripheral crossbar.
+    Gdma,
+    /// Peripheral-specific DMA, with one fixed channel per peripheral.
+    Pdma,
+}
+
+/// A single DMA controller instance, e.g. `DMA` or `GDMA`.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct DmaController {
+    pub name: String,
+    pub channels: u8,
+    pub kind: DmaKind,
+}
+
+/// DMA wiring configuration: the available controllers, and (per-driver) the
+/// request/channel binding for each instance that uses DMA.
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct DmaConfig {
+    #[serde(default)]
+    pub controllers: Vec<DmaController>,
+}
+
+/// Where a configuration value came from / what it currently holds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    /// The property is not set for this chip.
+    Unset,
+    /// A numeric property.
+    Number(u32),
+    /// A boolean property.
+    Boolean(bool),
+}
+
+/// The documentation/support status of a driver on a given chip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupportStatus {
+    /// The driver is not available on this chip.
+    NotSupported,
+    /// The driver is available, but with known limitations.
+    Partial,
+    /// The driver is fully supported.
+    Supported,
+}
+
+impl SupportStatus {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            SupportStatus::NotSupported => "",
+            SupportStatus::Partial => "🟡",
+            SupportStatus::Supported => "✅",
+        }
+    }
+
+    pub fn status(&self) -> &'static str {
+        match self {
+            SupportStatus::NotSupported => "Not supported",
+            SupportStatus::Partial => "Partially supported",
+            SupportStatus::Supported => "Supported",
+        }
+    }
+}
+
+/// A driver entry in the chip-support table.
+pub struct SupportItem {
+    pub name: &'static str,
+    pub symbols: &'static [&'static str],
+    pub config_group: &'static str,
+}
+
+/// GPIO pin capabilities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum PinCapability {
+    Input,
+    Output,
+    Analog,
+    Rtc,
+    Touch,
+    UsbDm,
+    UsbDp,
+}
+
+/// A single GPIO pin and the signals it can be muxed to.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct Pin {
+    pub pin: u8,
+    #[serde(default)]
+    pub kind: Vec<PinCapability>,
+    #[serde(default)]
+    pub alternate_functions: Vec<Option<String>>,
+}
+
+/// A named signal that can be routed through the IO MUX.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct IoMuxSignal {
+    pub name: String,
+    pub id: Option<u32>,
+}
+
+/// The full pin/signal table for a chip's GPIO peripheral.
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct PinsAndSignals {
+    #[serde(default)]
+    pub pins: Vec<Pin>,
+    #[serde(default)]
+    pub input_signals: Vec<IoMuxSignal>,
+    #[serde(default)]
+    pub output_signals: Vec<IoMuxSignal>,
+}
+
+/// GPIO driver configuration.
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct GpioConfig {
+    #[serde(default)]
+    pub remap_iomux_pin_registers: bool,
+    #[serde(default)]
+    pub pins_and_signals: PinsAndSignals,
+}
+
+/// Per-instance wiring for an I2C master peripheral.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct I2cMasterInstanceConfig {
+    pub sys_instance: String,
+    pub sda: String,
+    pub scl: String,
+    pub interrupt: String,
+    /// The DMA request/peripheral-selector signal this instance is wired to,
+    /// if it supports DMA.
+    #[serde(default)]
+    pub dma_request: Option<u32>,
+    /// The DMA channels this instance can legally be bound to.
+    #[serde(default)]
+    pub dma_channels: Vec<String>,
+}
+
+/// A single I2C master instance.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct I2cMasterInstance {
+    pub name: String,
+    #[serde(flatten)]
+    pub instance_config: I2cMasterInstanceConfig,
+}
+
+/// I2C master driver configuration.
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct I2cMasterConfig {
+    #[serde(default)]
+    pub instances: Vec<I2cMasterInstance>,
+    /// The IP-block revision implemented by this chip's I2C master
+    /// peripheral, e.g. `"v2"`. Lets drivers write `#[cfg(i2c_master_v2)]`
+    /// once instead of enumerating every chip that shares a revision.
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+impl PeriConfig {
+    /// The static list of drivers esp-metadata knows about, used to render
+    /// the chip-support table.
+    pub fn drivers() -> &'static [SupportItem] {
+        &[
+            SupportItem {
+                name: "GPIO",
+                symbols: &["gpio"],
+                config_group: "gpio",
+            },
+            SupportItem {
+                name: "I2C master",
+                symbols: &["i2c0", "i2c1"],
+                config_group: "i2c_master",
+            },
+        ]
+    }
+
+    /// `driver.instance` symbols for every configured driver instance, e.g.
+    /// `i2c_master.i2c0`.
+    pub fn driver_instances(&self) -> impl Iterator<Item = String> + '_ {
+        let i2c = self
+            .i2c_master
+            .iter()
+            .flat_map(|i2c| i2c.instances.iter())
+            .map(|instance| format!("i2c_master.{}", instance.name.to_lowercase()));
+
+        let dma = self
+            .dma
+            .iter()
+            .flat_map(|dma| dma.controllers.iter())
+            .map(|controller| format!("dma.{}", controller.name.to_lowercase()));
+
+        i2c.chain(dma)
+    }
+
+    /// The names of the drivers configured for this chip.
+    pub fn driver_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        let mut names = Vec::new();
+        if self.gpio.is_some() {
+            names.push("gpio");
+        }
+        if self.i2c_master.is_some() {
+            names.push("i2c_master");
+        }
+        if self.dma.is_some() {
+            names.push("dma");
+        }
+        names.into_iter()
+    }
+
+    /// Validates that every instance's `dma_channels` reference a controller
+    /// that is actually defined in `dma.controllers`.
+    pub fn validate_dma(&self) -> anyhow::Result<()> {
+        let controllers = self
+            .dma
+            .as_ref()
+            .map(|dma| dma.controllers.as_slice())
+            .unwrap_or(&[]);
+
+        if let Some(i2c) = &self.i2c_master {
+            for instance in &i2c.instances {
+                for channel in &instance.instance_config.dma_channels {
+                    anyhow::ensure!(
+                        controllers.iter().any(|c| &c.name == channel),
+                        "I2C instance '{}' references unknown DMA controller '{channel}'",
+                        instance.name
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Arbitrary numeric/boolean properties contributed by each driver.
+    pub fn properties(&self) -> impl Iterator<Item = (String, Value)> + '_ {
+        core::iter::empty()
+    }
+
+    /// `<driver>_<version>` cfg symbols for every driver that declares an
+    /// IP-block version, e.g. `i2c_master_v2`.
+    pub fn versioned_symbols(&self) -> impl Iterator<Item = String> + '_ {
+        self.i2c_master
+            .iter()
+            .filter_map(|i2c| i2c.version.as_deref())
+            .map(|version| format!("i2c_master_{version}"))
+    }
+
+    /// The `(driver, version)` pairs declared for this chip, used to emit
+    /// `property!("<driver>", version)` branches.
+    pub fn driver_versions(&self) -> impl Iterator<Item = (&'static str, &str)> + '_ {
+        self.i2c_master
+            .iter()
+            .filter_map(|i2c| i2c.version.as_deref())
+            .map(|version| ("i2c_master", version))
+    }
+
+    /// The support status of `config_group` on this chip.
+    pub fn support_status(&self, config_group: &str) -> Option<SupportStatus> {
+        match config_group {
+            "gpio" => self.gpio.as_ref().map(|_| SupportStatus::Supported),
+            "i2c_master" => self.i2c_master.as_ref().map(|_| SupportStatus::Supported),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Unset => write!(f, "unset"),
+            Value::Number(n) => write!(f, "{n}"),
+            Value::Boolean(b) => write!(f, "{b}"),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Inter-core primitive test
+//!
+//! These primitives (`Mailbox`, `Channel`, `Semaphore`) are lock-free
+//! algorithms built on plain atomics, so a single core exercising both ends
+//! itself is enough to catch an ordering bug in the CAS/sequence-number
+//! logic -- the interesting property under test is the algorithm, not an
+//! actual cross-core handoff.
+
+//% CHIPS: esp32c2 esp32c3 esp32c6 esp32h2 esp32s2 esp32s3 esp32
+
+#![no_std]
+#![no_main]
+
+use esp_hal::{ipc::{Channel, Semaphore}, mailbox::Mailbox};
+use hil_test as _;
+
+struct Context {}
+
+#[cfg(test)]
+#[embedded_test::tests]
+mod tests {
+    use super::*;
+
+    #[init]
+    fn init() -> Context {
+        esp_hal::init(esp_hal::Config::default());
+
+        Context {}
+    }
+
+    #[test]
+    fn mailbox_round_trips_values_in_order(_ctx: Context) {
+        let mailbox: Mailbox<u32, 4> = Mailbox::new();
+        let (mut tx, mut rx) = mailbox.split(None);
+
+        assert_eq!(rx.try_recv(), None);
+
+        tx.try_send(1).unwrap();
+        tx.try_send(2).unwrap();
+        assert_eq!(rx.try_recv(), Some(1));
+        tx.try_send(3).unwrap();
+        assert_eq!(rx.try_recv(), Some(2));
+        assert_eq!(rx.try_recv(), Some(3));
+        assert_eq!(rx.try_recv(), None);
+    }
+
+    #[test]
+    fn mailbox_try_send_fails_when_full(_ctx: Context) {
+        let mailbox: Mailbox<u32, 2> = Mailbox::new();
+        let (mut tx, mut rx) = mailbox.split(None);
+
+        tx.try_send(1).unwrap();
+        tx.try_send(2).unwrap();
+        assert_eq!(tx.try_send(3), Err(3));
+
+        assert_eq!(rx.try_recv(), Some(1));
+        tx.try_send(3).unwrap();
+        assert_eq!(rx.try_recv(), Some(2));
+        assert_eq!(rx.try_recv(), Some(3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn mailbox_split_twice_panics(_ctx: Context) {
+        let mailbox: Mailbox<u32, 2> = Mailbox::new();
+        let _first = mailbox.split(None);
+        let _second = mailbox.split(None);
+    }
+
+    #[test]
+    fn channel_enqueue_dequeue_respects_capacity(_ctx: Context) {
+        let channel: Channel<u32, 2> = Channel::new();
+
+        assert_eq!(channel.dequeue(), None);
+
+        channel.enqueue(1).unwrap();
+        channel.enqueue(2).unwrap();
+        assert_eq!(channel.enqueue(3), Err(3));
+
+        assert_eq!(channel.dequeue(), Some(1));
+        channel.enqueue(3).unwrap();
+        assert_eq!(channel.dequeue(), Some(2));
+        assert_eq!(channel.dequeue(), Some(3));
+        assert_eq!(channel.dequeue(), None);
+    }
+
+    #[test]
+    fn channel_reset_clears_outstanding_elements(_ctx: Context) {
+        let channel: Channel<u32, 2> = Channel::new();
+
+        channel.enqueue(1).unwrap();
+        channel.reset();
+
+        assert_eq!(channel.dequeue(), None);
+        channel.enqueue(2).unwrap();
+        channel.enqueue(3).unwrap();
+        assert_eq!(channel.dequeue(), Some(2));
+    }
+
+    #[test]
+    fn semaphore_try_acquire_and_release(_ctx: Context) {
+        let semaphore = Semaphore::new(1);
+
+        assert!(semaphore.try_acquire());
+        assert!(!semaphore.try_acquire());
+
+        semaphore.release();
+        assert!(semaphore.try_acquire());
+    }
+
+    #[test]
+    fn semaphore_reset_discards_pending_count(_ctx: Context) {
+        let semaphore = Semaphore::new(0);
+
+        assert!(!semaphore.try_acquire());
+        semaphore.reset(2);
+        assert!(semaphore.try_acquire());
+        assert!(semaphore.try_acquire());
+        assert!(!semaphore.try_acquire());
+    }
+}
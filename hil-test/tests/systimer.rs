@@ -6,7 +6,12 @@
 #![no_std]
 #![no_main]
 
-use core::cell::RefCell;
+use core::{
+    cell::RefCell,
+    future::Future,
+    pin::pin,
+    task::{Context as TaskContext, Poll, RawWaker, RawWakerVTable, Waker},
+};
 
 use critical_section::Mutex;
 use embedded_hal::delay::DelayNs;
@@ -73,6 +78,22 @@ fn pass_test_if_called_twice() {
     }
 }
 
+/// A `Waker` that does nothing when woken, for manually busy-polling a
+/// future instead of relying on an async executor to re-poll it.
+fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        raw_waker()
+    }
+    fn no_op(_: *const ()) {}
+
+    fn raw_waker() -> RawWaker {
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, no_op, no_op, no_op);
+        RawWaker::new(core::ptr::null(), &VTABLE)
+    }
+
+    unsafe { Waker::from_raw(raw_waker()) }
+}
+
 #[handler(priority = esp_hal::interrupt::Priority::min())]
 fn target_fail_test_if_called_twice() {
     critical_section::with(|cs| {
@@ -168,4 +189,20 @@ mod tests {
         // We'll end the test in the interrupt handler.
         loop {}
     }
+
+    #[test]
+    #[timeout(3)]
+    fn async_wait_until_is_handled(ctx: Context) {
+        let mut alarm0 = Alarm::new(ctx.comparator0, &ctx.unit);
+
+        // Manually busy-poll `wait`'s future rather than pulling in an async
+        // executor: the interesting property under test is the arm-after-
+        // unmask ordering `wait_until` relies on (see the comment in its body), which
+        // a one-shot `block_on`-style poll loop exercises just as well.
+        let waker = noop_waker();
+        let mut cx = TaskContext::from_waker(&waker);
+        let mut future = pin!(alarm0.wait(10u64.millis()));
+
+        while future.as_mut().poll(&mut cx).is_pending() {}
+    }
 }
@@ -7,24 +7,353 @@ use std::path::PathBuf;
 
 use esp_metadata::Chip;
 use rmcp::{
+    ErrorData as McpError,
     ServerHandler,
-    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
-    model::{Implementation, ServerCapabilities, ServerInfo},
+    handler::server::{
+        router::tool::{ToolRoute, ToolRouter},
+        wrapper::Parameters,
+    },
+    model::{
+        CallToolResult,
+        Content,
+        Implementation,
+        ListResourcesResult,
+        PaginatedRequestParam,
+        ProgressNotificationParam,
+        ReadResourceRequestParam,
+        ReadResourceResult,
+        Resource,
+        ResourceContents,
+        ServerCapabilities,
+        ServerInfo,
+        Tool,
+    },
     schemars,
+    service::{RequestContext, RoleServer},
     tool,
     tool_handler,
     tool_router,
 };
 use xtask::{Package, Version, commands::SemverCheckCmd};
 
+/// URI of the [`Resource`] listing workspace packages, backed by
+/// [`packages_resource_json`].
+const PACKAGES_RESOURCE_URI: &str = "esp-hal://packages";
+/// URI of the [`Resource`] listing per-chip capabilities, backed by
+/// [`chips_resource_json`].
+const CHIPS_RESOURCE_URI: &str = "esp-hal://chips";
+
+/// Builds the `esp-hal://packages` resource contents: name, version,
+/// manifest path, and features for every workspace member, read straight
+/// from `cargo metadata` so it can never drift from the actual manifests.
+fn packages_resource_json(workspace: &std::path::Path) -> String {
+    let metadata = std::process::Command::new("cargo")
+        .args(["metadata", "--format-version", "1", "--no-deps"])
+        .current_dir(workspace)
+        .output()
+        .ok()
+        .and_then(|out| serde_json::from_slice::<serde_json::Value>(&out.stdout).ok());
+
+    let Some(metadata) = metadata else {
+        return serde_json::json!({ "packages": [] }).to_string();
+    };
+
+    let packages: Vec<serde_json::Value> = metadata
+        .get("packages")
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+        .map(|p| {
+            serde_json::json!({
+                "name": p.get("name"),
+                "version": p.get("version"),
+                "manifest_path": p.get("manifest_path"),
+                "features": p
+                    .get("features")
+                    .and_then(|f| f.as_object())
+                    .map(|f| f.keys().cloned().collect::<Vec<_>>())
+                    .unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    serde_json::json!({ "packages": packages }).to_string()
+}
+
+/// Builds the `esp-hal://chips` resource contents: the per-chip capability
+/// table (architecture, core count, peripherals), sourced from
+/// `esp_metadata::Config` instead of a hand-maintained `match` so it can't
+/// go stale as new chips are added to `esp-metadata`.
+fn chips_resource_json() -> String {
+    use strum::IntoEnumIterator;
+
+    let chips: Vec<serde_json::Value> = Chip::iter()
+        .map(|chip| {
+            let config = esp_metadata::Config::for_chip(&chip);
+            serde_json::json!({
+                "chip": chip.to_string(),
+                "arch": config.arch().to_string(),
+                "cores": config.cores().to_string(),
+                "peripherals": config.peripherals(),
+                "has_lp_core": chip.has_lp_core(),
+            })
+        })
+        .collect();
+
+    serde_json::json!({ "chips": chips }).to_string()
+}
+
+/// URI of the [`Resource`] describing the xtask CLI's subcommands, backed by
+/// [`xtask_cli_schema_json`].
+const XTASK_CLI_RESOURCE_URI: &str = "esp-hal://xtask-cli";
+
+/// Converts a single `clap::Arg` into a schema fragment an agent can use to
+/// validate a value before it ever reaches a spawned process: its name,
+/// whether it takes a value, its possible values (e.g. chip enum variants),
+/// whether it's required, and whether it can be repeated.
+fn clap_arg_schema(arg: &clap::Arg) -> serde_json::Value {
+    let possible_values: Vec<String> = arg
+        .get_possible_values()
+        .iter()
+        .map(|v| v.get_name().to_string())
+        .collect();
+
+    serde_json::json!({
+        "name": arg.get_id().as_str(),
+        "takes_value": arg.get_action().takes_values(),
+        "required": arg.is_required_set(),
+        "repeatable": matches!(
+            arg.get_action(),
+            clap::ArgAction::Append | clap::ArgAction::Count
+        ),
+        "possible_values": possible_values,
+        "help": arg.get_help().map(|h| h.to_string()),
+    })
+}
+
+/// Recursively describes a `clap::Command` and its nested subcommands as a
+/// JSON schema: name, about text, and each argument via
+/// [`clap_arg_schema`].
+fn describe_clap_command(cmd: &clap::Command) -> serde_json::Value {
+    let args: Vec<serde_json::Value> = cmd.get_arguments().map(clap_arg_schema).collect();
+    let subcommands: Vec<serde_json::Value> =
+        cmd.get_subcommands().map(describe_clap_command).collect();
+
+    serde_json::json!({
+        "name": cmd.get_name(),
+        "about": cmd.get_about().map(|a| a.to_string()),
+        "args": args,
+        "subcommands": subcommands,
+    })
+}
+
+/// Builds the `esp-hal://xtask-cli` resource contents: the xtask CLI's full
+/// subcommand tree, with a derived JSON schema per argument.
+///
+/// This is a read-only companion to [`dynamic_cli_tool_router`]'s actual
+/// tools, useful for an agent that wants the whole tree in one read rather
+/// than listing tools.
+fn xtask_cli_schema_json() -> String {
+    describe_clap_command(&xtask::cli::command()).to_string()
+}
+
+/// Builds a [`ToolRoute`] for `cmd`'s JSON schema (see [`clap_arg_schema`]),
+/// a dotted `cli.<path>` name, and a handler that maps whichever of `cmd`'s
+/// args a caller sets back into `xtask <path> --arg value ...` before
+/// running it through [`XtaskMcpServer::run_xtask_command`].
+fn clap_command_tool_route(path: &[String], cmd: &clap::Command) -> ToolRoute<XtaskMcpServer> {
+    let name = format!("cli.{}", path.join("."));
+    let description = cmd
+        .get_about()
+        .map(|a| a.to_string())
+        .unwrap_or_else(|| name.clone());
+    let schema = serde_json::json!({
+        "type": "object",
+        "properties": cmd
+            .get_arguments()
+            .map(|arg| (arg.get_id().as_str().to_string(), clap_arg_schema(arg)))
+            .collect::<serde_json::Map<_, _>>(),
+    });
+    let Some(schema) = schema.as_object().cloned() else {
+        unreachable!("constructed above as an object");
+    };
+
+    let path = path.to_vec();
+    let tool = Tool::new(name, description, schema);
+
+    ToolRoute::new(tool, move |this: XtaskMcpServer, params: serde_json::Value| {
+        let path = path.clone();
+        async move {
+            let mut args: Vec<String> = path.clone();
+            if let Some(map) = params.as_object() {
+                for (key, value) in map {
+                    match value {
+                        serde_json::Value::Bool(true) => args.push(format!("--{key}")),
+                        serde_json::Value::Bool(false) => {}
+                        serde_json::Value::Null => {}
+                        serde_json::Value::Array(items) => {
+                            args.push(format!("--{key}"));
+                            args.push(to_csv(
+                                &items.iter().map(|v| v.to_string()).collect::<Vec<_>>(),
+                            ));
+                        }
+                        other => {
+                            args.push(format!("--{key}"));
+                            args.push(other.as_str().map(str::to_string).unwrap_or_else(|| other.to_string()));
+                        }
+                    }
+                }
+            }
+
+            let args_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+            let output = this.run_xtask_command(&args_refs);
+            Ok(CallToolResult::success(vec![Content::text(output)]))
+        }
+    })
+}
+
+/// Walks the xtask CLI's clap tree and registers one dynamically-built MCP
+/// tool per leaf subcommand (a `cmd` with no further subcommands of its
+/// own) that `config` allows, named `cli.<path>` -- e.g.
+/// `cli.build.documentation`.
+///
+/// Unlike the hand-written `#[tool]` methods in this file, these aren't
+/// backed by a concrete `Parameters<T>` type: their schema and argument
+/// mapping are both derived from the clap definitions at server startup, so
+/// a new clap subcommand gets an MCP tool without a matching `#[tool]`
+/// method ever being written. Combined with [`XtaskMcpServer::tool_router`]
+/// (the macro-generated router for the hand-written tools) via `+`, as is
+/// the standard way to merge multiple `ToolRouter`s in this crate's rmcp
+/// version.
+///
+/// A leaf is skipped entirely -- not just hidden behind a runtime check --
+/// when `config.is_tool_allowed` rejects it. The hand-written `publish`,
+/// `bump_version`, and `tag_releases` tools are gated by the same
+/// `esp-mcp.toml` allow-list (see their inline `is_tool_allowed` checks
+/// below); without this, the `cli.release.publish` etc. leaves would
+/// register as ungated duplicates of those exact destructive actions.
+/// Leaf names are snake_cased (`bump-version` -> `bump_version`) before the
+/// check so they line up with the hand-written tools' names.
+fn dynamic_cli_tool_router(config: &McpConfig) -> ToolRouter<XtaskMcpServer> {
+    fn walk(path: Vec<String>, cmd: &clap::Command, config: &McpConfig, router: &mut ToolRouter<XtaskMcpServer>) {
+        let subcommands: Vec<&clap::Command> = cmd.get_subcommands().collect();
+        if subcommands.is_empty() {
+            if !path.is_empty() {
+                let canonical_name = cmd.get_name().replace('-', "_");
+                if config.is_tool_allowed(&canonical_name) {
+                    router.add_route(clap_command_tool_route(&path, cmd));
+                }
+            }
+            return;
+        }
+
+        for sub in subcommands {
+            let mut sub_path = path.clone();
+            sub_path.push(sub.get_name().to_string());
+            walk(sub_path, sub, config, router);
+        }
+    }
+
+    let mut router = ToolRouter::new();
+    walk(Vec::new(), &xtask::cli::command(), config, &mut router);
+    router
+}
+
+/// On-disk configuration for the MCP server, loaded from `esp-mcp.toml` in
+/// the workspace root, if present.
+///
+/// Unlike the per-call [`Parameters`] structs, this is operator-controlled:
+/// it supplies defaults for parameters agents leave unset and lets an
+/// operator restrict what an untrusted agent can do with this server.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct McpConfig {
+    /// Chip assumed for any tool parameter left unset.
+    pub chip: Option<Chip>,
+    /// Toolchain assumed for any tool parameter left unset.
+    pub toolchain: Option<String>,
+    /// Packages in scope for this server. `None` means "all packages".
+    /// `list_packages` lists only these, and `build_package`,
+    /// `lint_packages`, `check_packages` and `publish` reject any other
+    /// package; the other tools do not check this list.
+    pub packages: Option<Vec<Package>>,
+    /// Packages excluded even when they'd otherwise be in scope.
+    #[serde(default)]
+    pub exclude_packages: Vec<Package>,
+    /// Tool names this server will run. `None` means "all tools". Used to
+    /// disable destructive tools (`publish`, `tag_releases`,
+    /// `bump_version`) when the server is exposed to an untrusted agent.
+    /// Only those three tools and the dynamically registered `cli.*` tools
+    /// check this list; the other hand-written tools always run.
+    pub allowed_tools: Option<Vec<String>>,
+}
+
+impl McpConfig {
+    fn load(workspace: &std::path::Path) -> Self {
+        std::fs::read_to_string(workspace.join("esp-mcp.toml"))
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn is_tool_allowed(&self, name: &str) -> bool {
+        self.allowed_tools
+            .as_ref()
+            .map_or(true, |allowed| allowed.iter().any(|t| t == name))
+    }
+
+    fn is_package_allowed(&self, package: &Package) -> bool {
+        if self.exclude_packages.contains(package) {
+            return false;
+        }
+        self.packages
+            .as_ref()
+            .map_or(true, |whitelist| whitelist.contains(package))
+    }
+}
+
 /// MCP server that exposes xtask operations as tools.
 #[derive(Clone)]
 pub struct XtaskMcpServer {
     workspace: PathBuf,
+    config: McpConfig,
     tool_router: ToolRouter<Self>,
 }
 
 impl XtaskMcpServer {
+    /// Applies the configured default chip when `chip` is unset.
+    fn resolve_chip(&self, chip: Option<Chip>) -> Option<Chip> {
+        chip.or(self.config.chip)
+    }
+
+    /// Applies the configured default toolchain when `toolchain` is unset.
+    fn resolve_toolchain(&self, toolchain: Option<String>) -> Option<String> {
+        toolchain.or_else(|| self.config.toolchain.clone())
+    }
+
+    /// Returns `Some(error)` if any of `packages` is out of scope for this
+    /// server's `esp-mcp.toml`.
+    fn reject_out_of_scope(&self, packages: &Option<Vec<Package>>) -> Option<CargoBuildResult> {
+        let out_of_scope: Vec<String> = packages
+            .iter()
+            .flatten()
+            .filter(|p| !self.config.is_package_allowed(p))
+            .map(|p| p.to_string())
+            .collect();
+
+        if out_of_scope.is_empty() {
+            return None;
+        }
+
+        Some(CargoBuildResult {
+            exit_code: -1,
+            raw_output: Some(format!(
+                "Package(s) out of scope for this server's esp-mcp.toml: {}",
+                out_of_scope.join(", ")
+            )),
+            ..Default::default()
+        })
+    }
+
     /// Execute a cargo xtask command and capture output.
     fn run_xtask_command(&self, args: &[&str]) -> String {
         use std::process::Command;
@@ -51,6 +380,754 @@ impl XtaskMcpServer {
             )
         }
     }
+
+    /// Execute a cargo xtask command with `--message-format json` and parse
+    /// the newline-delimited cargo JSON stream it produces into a
+    /// [CargoBuildResult], so callers get typed diagnostics instead of a
+    /// blob of text to regex against.
+    ///
+    /// Falls back to stuffing the raw stdout/stderr into `raw_output` if the
+    /// command's output doesn't contain any recognizable cargo JSON messages
+    /// (e.g. the underlying xtask subcommand doesn't forward the flag, or
+    /// the command failed before cargo ran at all).
+    fn run_cargo_json_command(&self, args: &[&str]) -> CargoBuildResult {
+        use std::process::Command;
+
+        let mut full_args: Vec<&str> = args.to_vec();
+        full_args.push("--message-format");
+        full_args.push("json");
+
+        let output = match Command::new("cargo")
+            .arg("xtask")
+            .args(&full_args)
+            .current_dir(&self.workspace)
+            .output()
+        {
+            Ok(output) => output,
+            Err(e) => {
+                return CargoBuildResult {
+                    exit_code: -1,
+                    raw_output: Some(format!("Failed to execute command: {}", e)),
+                    ..Default::default()
+                };
+            }
+        };
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let exit_code = output.status.code().unwrap_or(-1);
+        let (artifacts, diagnostics, saw_cargo_json) = parse_cargo_json_messages(&stdout);
+
+        if !saw_cargo_json {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return CargoBuildResult {
+                exit_code,
+                raw_output: Some(format!("{}\n{}", stdout, stderr)),
+                ..Default::default()
+            };
+        }
+
+        CargoBuildResult {
+            exit_code,
+            artifacts,
+            diagnostics,
+            raw_output: None,
+        }
+    }
+
+    /// Spawns `cargo xtask <args>`, forwarding each stdout/stderr line to
+    /// the caller as an MCP progress notification as soon as it arrives
+    /// (keyed to the request's progress token, if the client sent one)
+    /// instead of buffering the whole run before returning anything.
+    ///
+    /// Returns the combined, interleaved stdout/stderr log and the process's
+    /// exit code.
+    async fn spawn_and_stream(
+        &self,
+        args: Vec<String>,
+        context: &RequestContext<RoleServer>,
+    ) -> (String, i32) {
+        use std::{
+            io::{BufRead, BufReader},
+            process::{Command, Stdio},
+        };
+
+        let workspace = self.workspace.clone();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+
+        let handle = tokio::task::spawn_blocking(move || {
+            let mut child = match Command::new("cargo")
+                .arg("xtask")
+                .args(&args)
+                .current_dir(&workspace)
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+            {
+                Ok(child) => child,
+                Err(e) => {
+                    let _ = tx.send(format!("Failed to execute command: {e}"));
+                    return -1;
+                }
+            };
+
+            let stdout = child.stdout.take().expect("child spawned with piped stdout");
+            let stderr = child.stderr.take().expect("child spawned with piped stderr");
+
+            let stderr_tx = tx.clone();
+            let stderr_thread = std::thread::spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    let _ = stderr_tx.send(line);
+                }
+            });
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                let _ = tx.send(line);
+            }
+            let _ = stderr_thread.join();
+            drop(tx);
+
+            child.wait().ok().and_then(|status| status.code()).unwrap_or(-1)
+        });
+
+        let progress_token = context.meta.get_progress_token();
+        let mut log = String::new();
+        let mut progress = 0u32;
+
+        while let Some(line) = rx.recv().await {
+            progress += 1;
+            log.push_str(&line);
+            log.push('\n');
+
+            if let Some(ref token) = progress_token {
+                let _ = context
+                    .peer
+                    .notify_progress(ProgressNotificationParam {
+                        progress_token: token.clone(),
+                        progress,
+                        total: None,
+                        message: Some(line),
+                    })
+                    .await;
+            }
+        }
+
+        let exit_code = handle.await.unwrap_or(-1);
+
+        if let Some(token) = progress_token {
+            let _ = context
+                .peer
+                .notify_progress(ProgressNotificationParam {
+                    progress_token: token,
+                    progress,
+                    total: Some(progress),
+                    message: Some(format!("command finished with exit code {exit_code}")),
+                })
+                .await;
+        }
+
+        (log, exit_code)
+    }
+
+    /// Streaming counterpart of [`run_cargo_json_command`][Self::run_cargo_json_command].
+    async fn run_cargo_json_command_streaming(
+        &self,
+        mut args: Vec<String>,
+        context: &RequestContext<RoleServer>,
+    ) -> CargoBuildResult {
+        args.push("--message-format".to_string());
+        args.push("json".to_string());
+
+        let (log, exit_code) = self.spawn_and_stream(args, context).await;
+        let (artifacts, diagnostics, saw_cargo_json) = parse_cargo_json_messages(&log);
+
+        if !saw_cargo_json {
+            return CargoBuildResult {
+                exit_code,
+                raw_output: Some(log),
+                ..Default::default()
+            };
+        }
+
+        CargoBuildResult {
+            exit_code,
+            artifacts,
+            diagnostics,
+            raw_output: None,
+        }
+    }
+
+    /// Like [`run_xtask_command`][Self::run_xtask_command], but when
+    /// `report` is set, appends the flags needed to make the underlying test
+    /// binaries emit libtest's JSON event stream and aggregates that into a
+    /// JUnit report alongside the plain-text log.
+    fn run_test_command(&self, args: &[&str], suite_name: &str, report: bool) -> TestRunResult {
+        if !report {
+            return TestRunResult {
+                log: self.run_xtask_command(args),
+                ..Default::default()
+            };
+        }
+
+        let mut full_args: Vec<&str> = args.to_vec();
+        full_args.extend([
+            "--",
+            "-Z",
+            "unstable-options",
+            "--format",
+            "json",
+            "--report-time",
+        ]);
+
+        let log = self.run_xtask_command(&full_args);
+        let suite = parse_libtest_json(suite_name, &log);
+
+        TestRunResult {
+            tests: Some(suite.tests),
+            failures: Some(suite.failures),
+            junit_xml: Some(to_junit_xml(&suite)),
+            log,
+        }
+    }
+
+    /// Streaming counterpart of [`run_test_command`][Self::run_test_command].
+    async fn run_test_command_streaming(
+        &self,
+        mut args: Vec<String>,
+        suite_name: &str,
+        report: bool,
+        context: &RequestContext<RoleServer>,
+    ) -> TestRunResult {
+        if report {
+            args.extend(
+                ["--", "-Z", "unstable-options", "--format", "json", "--report-time"]
+                    .map(String::from),
+            );
+        }
+
+        let (log, _exit_code) = self.spawn_and_stream(args, context).await;
+
+        if !report {
+            return TestRunResult {
+                log,
+                ..Default::default()
+            };
+        }
+
+        let suite = parse_libtest_json(suite_name, &log);
+        TestRunResult {
+            tests: Some(suite.tests),
+            failures: Some(suite.failures),
+            junit_xml: Some(to_junit_xml(&suite)),
+            log,
+        }
+    }
+}
+
+/// A single cargo compiler diagnostic, extracted from a `compiler-message`
+/// line of cargo's `--message-format=json` output.
+#[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+pub struct CargoDiagnostic {
+    /// Severity, e.g. "error" or "warning".
+    pub level: String,
+    /// The diagnostic's primary message text.
+    pub message: String,
+    /// Source file of the diagnostic's primary span, if any.
+    pub file: Option<String>,
+    /// 1-based line of the diagnostic's primary span, if any.
+    pub line: Option<usize>,
+    /// 1-based column of the diagnostic's primary span, if any.
+    pub col: Option<usize>,
+    /// The fully rendered, human-readable diagnostic as rustc prints it.
+    pub rendered: Option<String>,
+}
+
+/// Structured result of a cargo invocation run through
+/// [`XtaskMcpServer::run_cargo_json_command`].
+#[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+pub struct CargoBuildResult {
+    /// Process exit code (`-1` if the command could not be spawned, or was
+    /// terminated by a signal).
+    pub exit_code: i32,
+    /// Paths of artifacts produced by `compiler-artifact` messages.
+    pub artifacts: Vec<String>,
+    /// Diagnostics extracted from `compiler-message` lines.
+    pub diagnostics: Vec<CargoDiagnostic>,
+    /// Raw combined stdout/stderr, populated instead of `artifacts`/
+    /// `diagnostics` when the output couldn't be parsed as cargo JSON.
+    pub raw_output: Option<String>,
+}
+
+/// A single test case parsed from libtest's JSON output, as emitted by
+/// `cargo test ... -- -Z unstable-options --format json --report-time`.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct TestCase {
+    /// Fully-qualified test name, as libtest reports it.
+    pub name: String,
+    /// Execution time in seconds, if libtest reported one.
+    pub time: f64,
+    /// Failure output, present only for failed tests.
+    pub failure_message: Option<String>,
+}
+
+/// A JUnit-style test suite, aggregated from libtest's per-test JSON events.
+#[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+pub struct TestSuite {
+    pub name: String,
+    pub tests: usize,
+    pub failures: usize,
+    pub time: f64,
+    pub cases: Vec<TestCase>,
+}
+
+/// Result of a test run, optionally including a JUnit report.
+#[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+pub struct TestRunResult {
+    /// Combined stdout/stderr from the test run.
+    pub log: String,
+    /// JUnit XML report, present when `report: true` was requested.
+    pub junit_xml: Option<String>,
+    /// Total number of tests parsed from the report.
+    pub tests: Option<usize>,
+    /// Total number of failed tests parsed from the report.
+    pub failures: Option<usize>,
+}
+
+/// A single binary-size measurement for one (chip, package, profile)
+/// combination, produced by the `metrics` tool and appended to the
+/// workspace's metrics log so later runs can diff against it.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+pub struct BinarySizeMetrics {
+    pub chip: String,
+    pub package: String,
+    pub profile: String,
+    /// Commit the build was made at, if `git rev-parse HEAD` succeeded.
+    pub git_rev: Option<String>,
+    pub text_bytes: u64,
+    pub rodata_bytes: u64,
+    pub data_bytes: u64,
+    pub bss_bytes: u64,
+    /// `text + rodata + data`: what has to fit in flash.
+    pub total_flash: u64,
+    /// `data + bss`: what has to fit in RAM at runtime.
+    pub total_ram: u64,
+}
+
+/// Per-section deltas between a fresh [`BinarySizeMetrics`] run and a
+/// baseline git ref's recorded measurement.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct BinarySizeDelta {
+    pub text_bytes: i64,
+    pub rodata_bytes: i64,
+    pub data_bytes: i64,
+    pub bss_bytes: i64,
+    pub total_flash: i64,
+    pub total_ram: i64,
+}
+
+/// Result of the `metrics` tool.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct MetricsResult {
+    pub metrics: BinarySizeMetrics,
+    /// The baseline measurement found for `baseline`, if one was requested
+    /// and a matching record existed in the metrics log.
+    pub baseline: Option<BinarySizeMetrics>,
+    pub delta: Option<BinarySizeDelta>,
+}
+
+/// Relative path of the append-only metrics log, under the workspace root.
+const METRICS_LOG_PATH: &str = "target/mcp-metrics.jsonl";
+
+/// Parses `size -A`'s per-section output into `(text, rodata, data, bss)`
+/// byte counts.
+fn parse_size_sections(output: &str) -> (u64, u64, u64, u64) {
+    let mut text = 0;
+    let mut rodata = 0;
+    let mut data = 0;
+    let mut bss = 0;
+
+    for line in output.lines() {
+        let mut columns = line.split_whitespace();
+        let Some(name) = columns.next() else { continue };
+        let Some(size) = columns.next().and_then(|s| s.parse::<u64>().ok()) else {
+            continue;
+        };
+
+        match name {
+            ".text" => text += size,
+            ".rodata" => rodata += size,
+            ".data" => data += size,
+            ".bss" => bss += size,
+            _ => {}
+        }
+    }
+
+    (text, rodata, data, bss)
+}
+
+/// Appends `record` as one line to the workspace's metrics log, creating it
+/// (and its parent directory) if this is the first run.
+fn append_metrics_record(workspace: &std::path::Path, record: &BinarySizeMetrics) {
+    use std::io::Write;
+
+    let path = workspace.join(METRICS_LOG_PATH);
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+
+    let Ok(line) = serde_json::to_string(record) else {
+        return;
+    };
+    if let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(&path) {
+        let _ = writeln!(file, "{line}");
+    }
+}
+
+/// Finds the most recent metrics log entry matching `chip`/`package`/
+/// `profile` recorded at `git_rev`.
+fn find_metrics_record(
+    workspace: &std::path::Path,
+    chip: &str,
+    package: &str,
+    profile: &str,
+    git_rev: &str,
+) -> Option<BinarySizeMetrics> {
+    let contents = std::fs::read_to_string(workspace.join(METRICS_LOG_PATH)).ok()?;
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<BinarySizeMetrics>(line).ok())
+        .filter(|m| {
+            m.chip == chip && m.package == package && m.profile == profile
+                && m.git_rev.as_deref() == Some(git_rev)
+        })
+        .last()
+}
+
+/// Resolves `rev` (a branch, tag, or short hash) to a full commit hash via
+/// `git rev-parse`, used both to stamp new metrics records and to look up a
+/// requested baseline.
+fn git_rev_parse(workspace: &std::path::Path, rev: &str) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", rev])
+        .current_dir(workspace)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Per-crate line coverage, part of a [`CoverageSummary`].
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct CrateCoverage {
+    pub name: String,
+    pub lines_covered: u64,
+    pub lines_total: u64,
+}
+
+/// Aggregated coverage numbers parsed from grcov's `covdir` JSON output.
+#[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+pub struct CoverageSummary {
+    pub lines_covered: u64,
+    pub lines_total: u64,
+    pub per_crate: Vec<CrateCoverage>,
+}
+
+/// Result of the `coverage` tool.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct CoverageResult {
+    /// The requested output format: "lcov", "html", or "json-summary".
+    pub format: String,
+    /// Path to the generated lcov file / HTML directory / JSON summary.
+    pub output_path: Option<String>,
+    /// Parsed summary, populated only for `format: "json-summary"`.
+    pub summary: Option<CoverageSummary>,
+    /// Combined stdout/stderr of the instrumented test run.
+    pub log: String,
+}
+
+/// Parses grcov's `covdir` JSON output (`linesCovered`/`linesValid` at the
+/// top level and per top-level-directory in `children`) into a
+/// [`CoverageSummary`].
+fn parse_covdir_summary(json: &str) -> CoverageSummary {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(json) else {
+        return CoverageSummary::default();
+    };
+
+    let per_crate = value
+        .get("children")
+        .and_then(|c| c.as_object())
+        .into_iter()
+        .flatten()
+        .map(|(name, child)| CrateCoverage {
+            name: name.clone(),
+            lines_covered: child.get("linesCovered").and_then(|v| v.as_u64()).unwrap_or(0),
+            lines_total: child.get("linesValid").and_then(|v| v.as_u64()).unwrap_or(0),
+        })
+        .collect();
+
+    CoverageSummary {
+        lines_covered: value.get("linesCovered").and_then(|v| v.as_u64()).unwrap_or(0),
+        lines_total: value.get("linesValid").and_then(|v| v.as_u64()).unwrap_or(0),
+        per_crate,
+    }
+}
+
+/// A single packaged firmware bundle produced by the `dist` tool.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct DistArchive {
+    pub chip: String,
+    /// Path of the gzip-compressed tar archive, relative to the workspace.
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+/// Result of the `dist` tool.
+#[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+pub struct DistResult {
+    pub archives: Vec<DistArchive>,
+    /// Combined stdout/stderr of the per-chip builds.
+    pub log: String,
+}
+
+/// Converts an ELF artifact to a flashable raw binary image alongside it,
+/// via `llvm-objcopy`. Returns `None` if the conversion failed, in which
+/// case the archive is packaged with just the ELF.
+fn objcopy_to_bin(elf_path: &std::path::Path) -> Option<PathBuf> {
+    let bin_path = elf_path.with_extension("bin");
+    let status = std::process::Command::new("llvm-objcopy")
+        .args(["-O", "binary"])
+        .arg(elf_path)
+        .arg(&bin_path)
+        .status()
+        .ok()?;
+
+    status.success().then_some(bin_path)
+}
+
+/// Packages `artifacts` (and, if produced, their `.bin` images) for one chip
+/// into a gzip-compressed tar archive under `dist/` in the workspace.
+fn package_chip_archive(
+    workspace: &std::path::Path,
+    chip: Chip,
+    artifacts: &[String],
+    strip: bool,
+) -> std::io::Result<DistArchive> {
+    let dist_dir = workspace.join("dist");
+    std::fs::create_dir_all(&dist_dir)?;
+
+    let archive_path = dist_dir.join(format!("esp-hal-{chip}.tar.gz"));
+    let file = std::fs::File::create(&archive_path)?;
+    let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::best());
+    let mut builder = tar::Builder::new(encoder);
+
+    for artifact in artifacts {
+        let elf_path = std::path::Path::new(artifact);
+        let Some(file_name) = elf_path.file_name() else {
+            continue;
+        };
+
+        if strip {
+            let _ = std::process::Command::new("llvm-strip").arg(elf_path).status();
+        }
+
+        builder.append_path_with_name(elf_path, file_name)?;
+
+        if let Some(bin_path) = objcopy_to_bin(elf_path) {
+            if let Some(bin_name) = bin_path.file_name() {
+                builder.append_path_with_name(&bin_path, bin_name)?;
+            }
+        }
+    }
+
+    builder.into_inner()?.finish()?;
+    let size_bytes = std::fs::metadata(&archive_path)?.len();
+
+    Ok(DistArchive {
+        chip: chip.to_string(),
+        path: archive_path
+            .strip_prefix(workspace)
+            .unwrap_or(&archive_path)
+            .display()
+            .to_string(),
+        size_bytes,
+    })
+}
+
+/// Parses cargo's `--message-format=json` newline-delimited stream into
+/// `(artifacts, diagnostics, saw_cargo_json)`. `saw_cargo_json` is `false`
+/// when no line in `stdout` was recognizable cargo JSON, signaling callers
+/// to fall back to returning the raw output.
+fn parse_cargo_json_messages(stdout: &str) -> (Vec<String>, Vec<CargoDiagnostic>, bool) {
+    let mut artifacts = Vec::new();
+    let mut diagnostics = Vec::new();
+    let mut saw_cargo_json = false;
+
+    for line in stdout.lines() {
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        let Some(reason) = value.get("reason").and_then(|r| r.as_str()) else {
+            continue;
+        };
+        saw_cargo_json = true;
+
+        match reason {
+            "compiler-message" => {
+                let Some(message) = value.get("message") else {
+                    continue;
+                };
+                let span = message
+                    .get("spans")
+                    .and_then(|s| s.as_array())
+                    .and_then(|spans| spans.first());
+
+                diagnostics.push(CargoDiagnostic {
+                    level: message
+                        .get("level")
+                        .and_then(|l| l.as_str())
+                        .unwrap_or("unknown")
+                        .to_string(),
+                    message: message
+                        .get("message")
+                        .and_then(|m| m.as_str())
+                        .unwrap_or_default()
+                        .to_string(),
+                    file: span
+                        .and_then(|s| s.get("file_name"))
+                        .and_then(|f| f.as_str())
+                        .map(str::to_string),
+                    line: span
+                        .and_then(|s| s.get("line_start"))
+                        .and_then(|l| l.as_u64())
+                        .map(|l| l as usize),
+                    col: span
+                        .and_then(|s| s.get("column_start"))
+                        .and_then(|c| c.as_u64())
+                        .map(|c| c as usize),
+                    rendered: message
+                        .get("rendered")
+                        .and_then(|r| r.as_str())
+                        .map(str::to_string),
+                });
+            }
+            "compiler-artifact" => {
+                if let Some(filenames) = value.get("filenames").and_then(|f| f.as_array()) {
+                    artifacts
+                        .extend(filenames.iter().filter_map(|f| f.as_str()).map(str::to_string));
+                }
+            }
+            // `build-finished`'s `success` field is already reflected in the
+            // process exit code; nothing further to record.
+            _ => {}
+        }
+    }
+
+    (artifacts, diagnostics, saw_cargo_json)
+}
+
+/// Escapes text for safe inclusion in XML attribute values and element text.
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Parses libtest's `--format json --report-time` output into a
+/// [TestSuite].
+fn parse_libtest_json(name: &str, stdout: &str) -> TestSuite {
+    let mut suite = TestSuite {
+        name: name.to_string(),
+        ..Default::default()
+    };
+
+    for line in stdout.lines() {
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        let Some(ty) = value.get("type").and_then(|t| t.as_str()) else {
+            continue;
+        };
+        let Some(event) = value.get("event").and_then(|e| e.as_str()) else {
+            continue;
+        };
+
+        match (ty, event) {
+            ("test", "ok") | ("test", "failed") | ("test", "ignored") => {
+                let case_name = value
+                    .get("name")
+                    .and_then(|n| n.as_str())
+                    .unwrap_or("<unknown>")
+                    .to_string();
+                let time = value
+                    .get("exec_time")
+                    .and_then(|t| t.as_f64())
+                    .unwrap_or(0.0);
+                let failure_message = if event == "failed" {
+                    Some(
+                        value
+                            .get("stdout")
+                            .and_then(|s| s.as_str())
+                            .unwrap_or("test failed")
+                            .to_string(),
+                    )
+                } else {
+                    None
+                };
+
+                if event == "failed" {
+                    suite.failures += 1;
+                }
+                suite.tests += 1;
+                suite.cases.push(TestCase {
+                    name: case_name,
+                    time,
+                    failure_message,
+                });
+            }
+            ("suite", "ok") | ("suite", "failed") => {
+                if let Some(time) = value.get("exec_time").and_then(|t| t.as_f64()) {
+                    suite.time = time;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    suite
+}
+
+/// Serializes a [TestSuite] to the standard JUnit `<testsuite>` XML schema.
+fn to_junit_xml(suite: &TestSuite) -> String {
+    let mut xml = format!(
+        "<testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{}\">\n",
+        escape_xml(&suite.name),
+        suite.tests,
+        suite.failures,
+        suite.time
+    );
+
+    for case in &suite.cases {
+        if let Some(ref failure) = case.failure_message {
+            xml += &format!(
+                "  <testcase name=\"{}\" time=\"{}\">\n    <failure>{}</failure>\n  </testcase>\n",
+                escape_xml(&case.name),
+                case.time,
+                escape_xml(failure)
+            );
+        } else {
+            xml += &format!(
+                "  <testcase name=\"{}\" time=\"{}\"/>\n",
+                escape_xml(&case.name),
+                case.time
+            );
+        }
+    }
+
+    xml += "</testsuite>\n";
+    xml
 }
 
 // ============================================================================
@@ -135,6 +1212,8 @@ pub struct RunTestsParams {
     pub repeat: Option<u32>,
     /// Toolchain to use.
     pub toolchain: Option<String>,
+    /// Produce a JUnit XML report alongside the human-readable log.
+    pub report: Option<bool>,
 }
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
@@ -179,6 +1258,8 @@ pub struct CheckChangelogParams {
 pub struct HostTestsParams {
     /// Packages to test.
     pub packages: Option<Vec<Package>>,
+    /// Produce a JUnit XML report alongside the human-readable log.
+    pub report: Option<bool>,
 }
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
@@ -249,6 +1330,52 @@ pub struct HelpParams {
     pub command: Option<String>,
 }
 
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct MetricsParams {
+    /// Target chip.
+    pub chip: Chip,
+    /// Name of the example to build, or omit to build all examples.
+    pub example: Option<String>,
+    /// Package containing the example(s) (defaults to "examples").
+    pub package: Option<Package>,
+    /// Build profile: "debug" or "release" (default "release").
+    pub profile: Option<String>,
+    /// Git ref to diff this run's sizes against, if a matching record for
+    /// it exists in the metrics log.
+    pub baseline: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct DistParams {
+    /// Chips to build and package firmware bundles for.
+    pub chips: Vec<Chip>,
+    /// Name of the example to build, or omit to package all examples.
+    pub example: Option<String>,
+    /// Build profile: "debug" or "release" (default "release").
+    pub profile: Option<String>,
+    /// Strip symbols from the packaged ELFs.
+    pub strip: Option<bool>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct CoverageParams {
+    /// Restrict the instrumented run to this package.
+    pub package: Option<Package>,
+    /// Output format: "lcov" (default), "html", or "json-summary".
+    pub format: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct ListFeaturesParams {
+    /// Restrict results to this package (e.g. "esp-hal" to enumerate its
+    /// chip-selection features). Omit to list every package.
+    pub package: Option<String>,
+    /// Feature names to drop from the results, e.g. noise features that
+    /// aren't meant to be passed via `--features`.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
 // ============================================================================
 // Tool implementations
 // ============================================================================
@@ -265,9 +1392,12 @@ fn to_csv<T: std::fmt::Display>(items: &[T]) -> String {
 #[tool_router]
 impl XtaskMcpServer {
     pub fn new(workspace: PathBuf) -> Self {
+        let config = McpConfig::load(&workspace);
+        let dynamic_router = dynamic_cli_tool_router(&config);
         Self {
             workspace,
-            tool_router: Self::tool_router(),
+            config,
+            tool_router: Self::tool_router() + dynamic_router,
         }
     }
 
@@ -297,37 +1427,51 @@ impl XtaskMcpServer {
         self.run_xtask_command(&args_refs)
     }
 
-    #[tool(description = "Build examples for a specific chip")]
-    fn build_examples(&self, Parameters(params): Parameters<BuildExamplesParams>) -> String {
+    #[tool(
+        description = "Build examples for a specific chip, streaming progress and returning parsed compiler diagnostics"
+    )]
+    async fn build_examples(
+        &self,
+        context: RequestContext<RoleServer>,
+        Parameters(params): Parameters<BuildExamplesParams>,
+    ) -> CargoBuildResult {
         let mut args = vec!["build".to_string(), "examples".to_string()];
         if let Some(ref e) = params.example {
             args.push(e.clone());
         }
-        let chip_str;
-        if let Some(ref c) = params.chip {
-            chip_str = c.to_string();
+        if let Some(c) = self.resolve_chip(params.chip) {
             args.push("--chip".to_string());
-            args.push(chip_str.clone());
+            args.push(c.to_string());
         }
-        let package_str;
         if let Some(ref p) = params.package {
-            package_str = p.to_string();
             args.push("--package".to_string());
-            args.push(package_str.clone());
+            args.push(p.to_string());
         }
         if params.debug == Some(true) {
             args.push("--debug".to_string());
         }
-        if let Some(ref t) = params.toolchain {
+        if let Some(t) = self.resolve_toolchain(params.toolchain) {
             args.push("--toolchain".to_string());
-            args.push(t.clone());
+            args.push(t);
         }
-        let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-        self.run_xtask_command(&args_refs)
+        self.run_cargo_json_command_streaming(args, &context).await
     }
 
-    #[tool(description = "Build a specific package with custom options")]
-    fn build_package(&self, Parameters(params): Parameters<BuildPackageParams>) -> String {
+    #[tool(
+        description = "Build a specific package with custom options, returning parsed compiler diagnostics"
+    )]
+    fn build_package(&self, Parameters(params): Parameters<BuildPackageParams>) -> CargoBuildResult {
+        if !self.config.is_package_allowed(&params.package) {
+            return CargoBuildResult {
+                exit_code: -1,
+                raw_output: Some(format!(
+                    "Package {} is out of scope for this server's esp-mcp.toml.",
+                    params.package
+                )),
+                ..Default::default()
+            };
+        }
+
         let package_str = params.package.to_string();
         let mut args = vec!["build".to_string(), "package".to_string(), package_str];
         if let Some(ref t) = params.target {
@@ -338,15 +1482,15 @@ impl XtaskMcpServer {
             args.push("--features".to_string());
             args.push(f.clone());
         }
-        if let Some(ref tc) = params.toolchain {
+        if let Some(tc) = self.resolve_toolchain(params.toolchain) {
             args.push("--toolchain".to_string());
-            args.push(tc.clone());
+            args.push(tc);
         }
         if params.no_default_features == Some(true) {
             args.push("--no-default-features".to_string());
         }
         let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-        self.run_xtask_command(&args_refs)
+        self.run_cargo_json_command(&args_refs)
     }
 
     #[tool(description = "Build tests for a specific chip")]
@@ -386,30 +1530,32 @@ impl XtaskMcpServer {
             "example".to_string(),
             params.example.clone(),
         ];
-        let chip_str;
-        if let Some(ref c) = params.chip {
-            chip_str = c.to_string();
+        if let Some(c) = self.resolve_chip(params.chip) {
             args.push("--chip".to_string());
-            args.push(chip_str.clone());
+            args.push(c.to_string());
         }
-        let package_str;
         if let Some(ref p) = params.package {
-            package_str = p.to_string();
             args.push("--package".to_string());
-            args.push(package_str.clone());
+            args.push(p.to_string());
         }
-        if let Some(ref tc) = params.toolchain {
+        if let Some(tc) = self.resolve_toolchain(params.toolchain) {
             args.push("--toolchain".to_string());
-            args.push(tc.clone());
+            args.push(tc);
         }
         let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
         self.run_xtask_command(&args_refs)
     }
 
-    #[tool(description = "Run tests for a specific chip")]
-    fn run_tests(&self, Parameters(params): Parameters<RunTestsParams>) -> String {
+    #[tool(
+        description = "Run tests for a specific chip, streaming progress and optionally producing a JUnit XML report"
+    )]
+    async fn run_tests(
+        &self,
+        context: RequestContext<RoleServer>,
+        Parameters(params): Parameters<RunTestsParams>,
+    ) -> TestRunResult {
         let chip_str = params.chip.to_string();
-        let mut args = vec!["run".to_string(), "tests".to_string(), chip_str];
+        let mut args = vec!["run".to_string(), "tests".to_string(), chip_str.clone()];
         if let Some(ref t) = params.test {
             args.push("--test".to_string());
             args.push(t.clone());
@@ -418,12 +1564,17 @@ impl XtaskMcpServer {
             args.push("--repeat".to_string());
             args.push(r.to_string());
         }
-        if let Some(ref tc) = params.toolchain {
+        if let Some(tc) = self.resolve_toolchain(params.toolchain) {
             args.push("--toolchain".to_string());
-            args.push(tc.clone());
+            args.push(tc);
         }
-        let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-        self.run_xtask_command(&args_refs)
+        self.run_test_command_streaming(
+            args,
+            &format!("run-tests-{chip_str}"),
+            params.report == Some(true),
+            &context,
+        )
+        .await
     }
 
     #[tool(description = "Format all packages in the workspace with rustfmt")]
@@ -441,8 +1592,14 @@ impl XtaskMcpServer {
         self.run_xtask_command(&args_refs)
     }
 
-    #[tool(description = "Lint all packages in the workspace with clippy")]
-    fn lint_packages(&self, Parameters(params): Parameters<LintPackagesParams>) -> String {
+    #[tool(
+        description = "Lint all packages in the workspace with clippy, returning parsed compiler diagnostics"
+    )]
+    fn lint_packages(&self, Parameters(params): Parameters<LintPackagesParams>) -> CargoBuildResult {
+        if let Some(result) = self.reject_out_of_scope(&params.packages) {
+            return result;
+        }
+
         let mut args = vec!["lint-packages".to_string()];
         let packages_str;
         if let Some(ref p) = params.packages {
@@ -458,16 +1615,22 @@ impl XtaskMcpServer {
         if params.fix == Some(true) {
             args.push("--fix".to_string());
         }
-        if let Some(ref tc) = params.toolchain {
+        if let Some(tc) = self.resolve_toolchain(params.toolchain) {
             args.push("--toolchain".to_string());
-            args.push(tc.clone());
+            args.push(tc);
         }
         let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-        self.run_xtask_command(&args_refs)
+        self.run_cargo_json_command(&args_refs)
     }
 
-    #[tool(description = "Check all packages with cargo check")]
-    fn check_packages(&self, Parameters(params): Parameters<CheckPackagesParams>) -> String {
+    #[tool(
+        description = "Check all packages with cargo check, returning parsed compiler diagnostics"
+    )]
+    fn check_packages(&self, Parameters(params): Parameters<CheckPackagesParams>) -> CargoBuildResult {
+        if let Some(result) = self.reject_out_of_scope(&params.packages) {
+            return result;
+        }
+
         let mut args = vec!["check-packages".to_string()];
         let packages_str;
         if let Some(ref p) = params.packages {
@@ -480,12 +1643,12 @@ impl XtaskMcpServer {
             args.push("--chips".to_string());
             args.push(chips_str.clone());
         }
-        if let Some(ref tc) = params.toolchain {
+        if let Some(tc) = self.resolve_toolchain(params.toolchain) {
             args.push("--toolchain".to_string());
-            args.push(tc.clone());
+            args.push(tc);
         }
         let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-        self.run_xtask_command(&args_refs)
+        self.run_cargo_json_command(&args_refs)
     }
 
     #[tool(description = "Check the changelog for packages")]
@@ -504,8 +1667,10 @@ impl XtaskMcpServer {
         self.run_xtask_command(&args_refs)
     }
 
-    #[tool(description = "Run host tests in the workspace")]
-    fn host_tests(&self, Parameters(params): Parameters<HostTestsParams>) -> String {
+    #[tool(
+        description = "Run host tests in the workspace, optionally producing a JUnit XML report"
+    )]
+    fn host_tests(&self, Parameters(params): Parameters<HostTestsParams>) -> TestRunResult {
         let mut args = vec!["host-tests".to_string()];
         let packages_str;
         if let Some(ref p) = params.packages {
@@ -513,7 +1678,126 @@ impl XtaskMcpServer {
             args.push(packages_str.clone());
         }
         let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-        self.run_xtask_command(&args_refs)
+        self.run_test_command(&args_refs, "host-tests", params.report == Some(true))
+    }
+
+    #[tool(
+        description = "Run the host-side test suites under `-C instrument-coverage`, merge the \
+                        resulting profraw files with grcov, and return an lcov file, HTML \
+                        report, or JSON coverage summary"
+    )]
+    fn coverage(&self, Parameters(params): Parameters<CoverageParams>) -> CoverageResult {
+        let format = params.format.clone().unwrap_or_else(|| "lcov".to_string());
+
+        let profraw_dir = self.workspace.join("target/coverage/profraw");
+        let _ = std::fs::create_dir_all(&profraw_dir);
+        if let Ok(entries) = std::fs::read_dir(&profraw_dir) {
+            for entry in entries.flatten() {
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
+
+        let mut args = vec!["host-tests".to_string()];
+        if let Some(ref p) = params.package {
+            args.push(p.to_string());
+        }
+
+        let log = match std::process::Command::new("cargo")
+            .arg("xtask")
+            .args(&args)
+            .current_dir(&self.workspace)
+            .env("RUSTFLAGS", "-C instrument-coverage")
+            .env("LLVM_PROFILE_FILE", profraw_dir.join("%p-%m.profraw"))
+            .output()
+        {
+            Ok(output) => format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            ),
+            Err(e) => {
+                return CoverageResult {
+                    format,
+                    output_path: None,
+                    summary: None,
+                    log: format!("Failed to run instrumented host tests: {e}"),
+                };
+            }
+        };
+
+        let (grcov_type, relative_output) = match format.as_str() {
+            "html" => ("html", "target/coverage/html"),
+            "json-summary" => ("covdir", "target/coverage/coverage.json"),
+            _ => ("lcov", "target/coverage/lcov.info"),
+        };
+        let output_path = self.workspace.join(relative_output);
+        if let Some(parent) = output_path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+
+        let grcov_ok = std::process::Command::new("grcov")
+            .arg(&profraw_dir)
+            .args(["--binary-path", "target/debug"])
+            .args(["-s", "."])
+            .args(["-t", grcov_type])
+            .args(["--branch", "--ignore-not-existing"])
+            .arg("-o")
+            .arg(&output_path)
+            .current_dir(&self.workspace)
+            .status()
+            .is_ok_and(|status| status.success());
+
+        let summary = (grcov_ok && format == "json-summary")
+            .then(|| std::fs::read_to_string(&output_path).ok())
+            .flatten()
+            .map(|json| parse_covdir_summary(&json));
+
+        CoverageResult {
+            format,
+            output_path: Some(output_path.display().to_string()),
+            summary,
+            log,
+        }
+    }
+
+    #[tool(
+        description = "Build examples/firmware for one or more chips and package the resulting \
+                        ELFs (and, where possible, flashable .bin images) into per-chip \
+                        gzip-compressed tar archives under dist/"
+    )]
+    fn dist(&self, Parameters(params): Parameters<DistParams>) -> DistResult {
+        let profile = params.profile.unwrap_or_else(|| "release".to_string());
+        let strip = params.strip == Some(true);
+
+        let mut log = String::new();
+        let mut archives = Vec::new();
+
+        for chip in params.chips {
+            let mut args = vec!["build".to_string(), "examples".to_string()];
+            if let Some(ref example) = params.example {
+                args.push(example.clone());
+            }
+            args.push("--chip".to_string());
+            args.push(chip.to_string());
+            if profile == "debug" {
+                args.push("--debug".to_string());
+            }
+            args.push("--message-format".to_string());
+            args.push("json".to_string());
+
+            let args_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+            let build_log = self.run_xtask_command(&args_refs);
+            let (artifacts, _diagnostics, _saw_cargo_json) = parse_cargo_json_messages(&build_log);
+
+            log.push_str(&format!("== {chip} ==\n{build_log}\n"));
+
+            match package_chip_archive(&self.workspace, chip, &artifacts, strip) {
+                Ok(archive) => archives.push(archive),
+                Err(e) => log.push_str(&format!("Failed to package {chip}: {e}\n")),
+            }
+        }
+
+        DistResult { archives, log }
     }
 
     #[tool(description = "Re-generate metadata and the chip support table")]
@@ -526,13 +1810,17 @@ impl XtaskMcpServer {
         self.run_xtask_command(&args_refs)
     }
 
-    #[tool(description = "Run CI checks for a specific chip")]
-    fn ci(&self, Parameters(params): Parameters<CiParams>) -> String {
+    #[tool(description = "Run CI checks for a specific chip, streaming progress as it runs")]
+    async fn ci(
+        &self,
+        context: RequestContext<RoleServer>,
+        Parameters(params): Parameters<CiParams>,
+    ) -> String {
         let chip_str = params.chip.to_string();
         let mut args = vec!["ci".to_string(), chip_str];
-        if let Some(ref tc) = params.toolchain {
+        if let Some(tc) = self.resolve_toolchain(params.toolchain) {
             args.push("--toolchain".to_string());
-            args.push(tc.clone());
+            args.push(tc);
         }
         if params.no_lint == Some(true) {
             args.push("--no-lint".to_string());
@@ -543,8 +1831,12 @@ impl XtaskMcpServer {
         if params.no_check_crates == Some(true) {
             args.push("--no-check-crates".to_string());
         }
-        let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-        self.run_xtask_command(&args_refs)
+        let (log, exit_code) = self.spawn_and_stream(args, &context).await;
+        if exit_code == 0 {
+            log
+        } else {
+            format!("Command failed with exit code {exit_code}:\n{log}")
+        }
     }
 
     #[tool(description = "Clean build artifacts for packages")]
@@ -559,6 +1851,84 @@ impl XtaskMcpServer {
         self.run_xtask_command(&args_refs)
     }
 
+    #[tool(
+        description = "Build an example for a chip and return its ELF section sizes as \
+                        structured JSON (text/rodata/data/bss, total flash/RAM), appending the \
+                        result to the workspace's metrics log so it can be diffed against a \
+                        baseline git ref on a later run"
+    )]
+    fn metrics(&self, Parameters(params): Parameters<MetricsParams>) -> MetricsResult {
+        let chip = params.chip;
+        let profile = params.profile.clone().unwrap_or_else(|| "release".to_string());
+        let package = params.package.map(|p| p.to_string()).unwrap_or_else(|| "examples".to_string());
+
+        let mut args = vec!["build".to_string(), "examples".to_string()];
+        if let Some(ref example) = params.example {
+            args.push(example.clone());
+        }
+        args.push("--chip".to_string());
+        args.push(chip.to_string());
+        args.push("--package".to_string());
+        args.push(package.clone());
+        if profile == "debug" {
+            args.push("--debug".to_string());
+        }
+        args.push("--message-format".to_string());
+        args.push("json".to_string());
+        let args_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        let build_log = self.run_xtask_command(&args_refs);
+        let (artifacts, _diagnostics, _saw_cargo_json) = parse_cargo_json_messages(&build_log);
+
+        let git_rev = git_rev_parse(&self.workspace, "HEAD");
+        let (text_bytes, rodata_bytes, data_bytes, bss_bytes) = match artifacts.last() {
+            Some(artifact) => {
+                let size_output = std::process::Command::new("size")
+                    .arg("-A")
+                    .arg(artifact)
+                    .output()
+                    .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+                    .unwrap_or_default();
+                parse_size_sections(&size_output)
+            }
+            None => (0, 0, 0, 0),
+        };
+
+        let metrics = BinarySizeMetrics {
+            chip: chip.to_string(),
+            package,
+            profile,
+            git_rev,
+            text_bytes,
+            rodata_bytes,
+            data_bytes,
+            bss_bytes,
+            total_flash: text_bytes + rodata_bytes + data_bytes,
+            total_ram: data_bytes + bss_bytes,
+        };
+        append_metrics_record(&self.workspace, &metrics);
+
+        let baseline = params.baseline.as_deref().and_then(|baseline_ref| {
+            let baseline_rev = git_rev_parse(&self.workspace, baseline_ref)?;
+            find_metrics_record(
+                &self.workspace,
+                &metrics.chip,
+                &metrics.package,
+                &metrics.profile,
+                &baseline_rev,
+            )
+        });
+        let delta = baseline.as_ref().map(|b| BinarySizeDelta {
+            text_bytes: metrics.text_bytes as i64 - b.text_bytes as i64,
+            rodata_bytes: metrics.rodata_bytes as i64 - b.rodata_bytes as i64,
+            data_bytes: metrics.data_bytes as i64 - b.data_bytes as i64,
+            bss_bytes: metrics.bss_bytes as i64 - b.bss_bytes as i64,
+            total_flash: metrics.total_flash as i64 - b.total_flash as i64,
+            total_ram: metrics.total_ram as i64 - b.total_ram as i64,
+        });
+
+        MetricsResult { metrics, baseline, delta }
+    }
+
     #[tool(description = "Run semver checks on packages")]
     fn semver_check(&self, Parameters(params): Parameters<SemverCheckParams>) -> String {
         let action_str = match params.action {
@@ -585,6 +1955,9 @@ impl XtaskMcpServer {
 
     #[tool(description = "Bump the version of specified packages")]
     fn bump_version(&self, Parameters(params): Parameters<BumpVersionParams>) -> String {
+        if !self.config.is_tool_allowed("bump_version") {
+            return "The `bump_version` tool is disabled by this server's esp-mcp.toml.".into();
+        }
         let bump_str = params.bump.to_string();
         let mut args = vec!["release".to_string(), "bump-version".to_string(), bump_str];
         let packages_str;
@@ -602,6 +1975,15 @@ impl XtaskMcpServer {
 
     #[tool(description = "Publish a package to crates.io")]
     fn publish(&self, Parameters(params): Parameters<PublishParams>) -> String {
+        if !self.config.is_tool_allowed("publish") {
+            return "The `publish` tool is disabled by this server's esp-mcp.toml.".into();
+        }
+        if !self.config.is_package_allowed(&params.package) {
+            return format!(
+                "Package {} is out of scope for this server's esp-mcp.toml.",
+                params.package
+            );
+        }
         let package_str = params.package.to_string();
         let mut args = vec!["release".to_string(), "publish".to_string(), package_str];
         if params.dry_run == Some(true) {
@@ -613,6 +1995,9 @@ impl XtaskMcpServer {
 
     #[tool(description = "Generate git tags for package releases")]
     fn tag_releases(&self, Parameters(params): Parameters<TagReleasesParams>) -> String {
+        if !self.config.is_tool_allowed("tag_releases") {
+            return "The `tag_releases` tool is disabled by this server's esp-mcp.toml.".into();
+        }
         let mut args = vec!["release".to_string(), "tag-releases".to_string()];
         let packages_str;
         if let Some(ref p) = params.packages {
@@ -631,11 +2016,45 @@ impl XtaskMcpServer {
     fn list_packages(&self) -> String {
         use strum::IntoEnumIterator;
         Package::iter()
+            .filter(|p| self.config.is_package_allowed(p))
             .map(|p| p.to_string())
             .collect::<Vec<_>>()
             .join("\n")
     }
 
+    #[tool(
+        description = "List Cargo features available in the workspace, keyed by package, so \
+                        callers don't have to guess or grep Cargo.toml for valid --features \
+                        strings"
+    )]
+    fn list_features(&self, Parameters(params): Parameters<ListFeaturesParams>) -> String {
+        let metadata = match cargo_metadata::MetadataCommand::new()
+            .no_deps()
+            .current_dir(&self.workspace)
+            .exec()
+        {
+            Ok(metadata) => metadata,
+            Err(e) => return format!("Failed to run cargo metadata: {e}"),
+        };
+
+        let lines: Vec<String> = metadata
+            .packages
+            .iter()
+            .filter(|p| params.package.as_deref().is_none_or(|name| p.name == name))
+            .map(|p| {
+                let features: Vec<&str> = p
+                    .features
+                    .keys()
+                    .map(String::as_str)
+                    .filter(|f| !params.exclude.iter().any(|e| e == f))
+                    .collect();
+                format!("{}: {}", p.name, features.join(", "))
+            })
+            .collect();
+
+        lines.join("\n")
+    }
+
     #[tool(description = "List all supported ESP32 chips")]
     fn list_chips(&self) -> String {
         use strum::IntoEnumIterator;
@@ -678,10 +2097,90 @@ impl ServerHandler for XtaskMcpServer {
             .unwrap_or_else(|_| "esp-hal xtask MCP server. See .github/copilot-instructions.md for usage.".into());
 
         ServerInfo {
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_resources()
+                .build(),
             server_info: Implementation::from_build_env(),
             instructions: Some(instructions),
             ..Default::default()
         }
     }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        Ok(ListResourcesResult {
+            resources: vec![
+                Resource::new(
+                    rmcp::model::RawResource {
+                        uri: PACKAGES_RESOURCE_URI.to_string(),
+                        name: "esp-hal workspace packages".to_string(),
+                        description: Some(
+                            "Name, version, manifest path, and features for every workspace \
+                             member, read from `cargo metadata`."
+                                .to_string(),
+                        ),
+                        mime_type: Some("application/json".to_string()),
+                        size: None,
+                    },
+                    None,
+                ),
+                Resource::new(
+                    rmcp::model::RawResource {
+                        uri: CHIPS_RESOURCE_URI.to_string(),
+                        name: "esp-hal supported chips".to_string(),
+                        description: Some(
+                            "Per-chip capability table (architecture, core count, peripherals), \
+                             sourced from esp-metadata."
+                                .to_string(),
+                        ),
+                        mime_type: Some("application/json".to_string()),
+                        size: None,
+                    },
+                    None,
+                ),
+                Resource::new(
+                    rmcp::model::RawResource {
+                        uri: XTASK_CLI_RESOURCE_URI.to_string(),
+                        name: "xtask CLI schema".to_string(),
+                        description: Some(
+                            "xtask's subcommand tree, derived straight from its clap \
+                             definitions: argument names, possible values, required/optional, \
+                             and repeatability."
+                                .to_string(),
+                        ),
+                        mime_type: Some("application/json".to_string()),
+                        size: None,
+                    },
+                    None,
+                ),
+            ],
+            next_cursor: None,
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let contents = match request.uri.as_str() {
+            PACKAGES_RESOURCE_URI => packages_resource_json(&self.workspace),
+            CHIPS_RESOURCE_URI => chips_resource_json(),
+            XTASK_CLI_RESOURCE_URI => xtask_cli_schema_json(),
+            other => {
+                return Err(McpError::resource_not_found(
+                    format!("unknown resource: {other}"),
+                    None,
+                ));
+            }
+        };
+
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::text(contents, request.uri)],
+        })
+    }
 }